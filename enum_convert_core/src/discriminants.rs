@@ -0,0 +1,48 @@
+//! The discriminants of the variants, converted from or into integers.
+use proc_macro2::{Literal, TokenStream};
+use quote::quote;
+use syn::{Ident, Variant};
+
+/// The primitive integer types which can be listed in the container annotations, to convert the
+/// variants from or into their discriminants.
+const INTEGER_TYPES: [&str; 12] = [
+    "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+];
+
+/// Whether the identifier is one of the primitive integer types.
+pub(crate) fn is_integer_type(ident: &Ident) -> bool {
+    INTEGER_TYPES
+        .iter()
+        .any(|integer_type| ident == integer_type)
+}
+
+/// The discriminants of the variants, either explicit or following the previous one as the
+/// compiler does.
+/// They are cast into the integer types like the fieldless enums, the overflowing literals being
+/// rejected by the compiler.
+pub fn discriminants<'a>(variants: impl IntoIterator<Item = &'a Variant>) -> Vec<TokenStream> {
+    let mut last_explicit = None;
+    let mut offset = 0;
+    variants
+        .into_iter()
+        .map(|variant| {
+            if let Some((_, discriminant)) = &variant.discriminant {
+                last_explicit = Some(discriminant);
+                offset = 0;
+            } else {
+                offset += 1;
+            }
+            match last_explicit {
+                Some(discriminant) if offset == 0 => quote! { (#discriminant) },
+                Some(discriminant) => {
+                    let offset = Literal::usize_unsuffixed(offset);
+                    quote! { (#discriminant) + #offset }
+                }
+                None => {
+                    let discriminant = Literal::usize_unsuffixed(offset - 1);
+                    quote! { #discriminant }
+                }
+            }
+        })
+        .collect()
+}
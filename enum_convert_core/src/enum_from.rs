@@ -0,0 +1,6 @@
+//! The `#[enum_from]` annotations of `EnumFrom` and `EnumFromAsync`, converting the variants of
+//! source enums into the variants of the annotated enum.
+pub mod mapping;
+pub mod parser;
+
+pub use self::{mapping::EnumFromMapping, parser::ParsedEnumFrom};
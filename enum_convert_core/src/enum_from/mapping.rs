@@ -0,0 +1,960 @@
+//! The validated mapping of the source variants into the variants of the annotated enum.
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use indexmap::{IndexMap, map::Entry};
+use proc_macro2::{Group, Span, TokenStream};
+use syn::{Expr, Fields, FieldsNamed, FieldsUnnamed, Ident, Pat, Variant, spanned::Spanned};
+
+use crate::{
+    discriminants::discriminants,
+    enum_from::parser::{
+        ContainerAnnotation, ContainerOptions, FieldAnnotation, FieldAnnotations, FieldOptions,
+        ParsedEnumFrom, Payload, VariantAnnotation,
+    },
+    errors::{CollectErrors, Errors, sorted_by_name},
+    idents::{ContainerIdent, FieldIdent, FieldRef, VariantIdent},
+    renaming::Renaming,
+    suggestions::did_you_mean,
+};
+
+/// The conversions into the annotated enum, checked against its definition.
+/// Once constructed, the code generation should not fail.
+pub struct EnumFromMapping {
+    /// The source enums, in the order of the container annotations.
+    pub source_enums: IndexMap<ContainerIdent, SourceEnum>,
+    /// The annotated enum.
+    pub target_enum: ContainerIdent,
+    /// The variants of the annotated enum, in declaration order.
+    pub target_variants: IndexMap<VariantIdent, TargetVariant>,
+    /// The integer types converted into the annotated enum.
+    pub integer_sources: Vec<IntegerSource>,
+    /// The unit target variants which are not skipped, with their discriminants, in declaration
+    /// order.
+    pub discriminants: Vec<(VariantIdent, TokenStream)>,
+}
+
+/// An integer type converted into the unit variants of the target enum.
+pub struct IntegerSource {
+    /// The integer type.
+    pub integer_type: Ident,
+    /// The integers converted into each variant, in their declaration order.
+    pub arms: Vec<(VariantIdent, IntegerPattern)>,
+}
+
+/// The integers converted into a unit variant.
+pub enum IntegerPattern {
+    /// The patterns of the variant annotations.
+    Patterns(Vec<Pat>),
+    /// The discriminant of the variant, as an expression.
+    Discriminant(TokenStream),
+}
+
+/// A source enum with the conversions of its variants.
+pub struct SourceEnum {
+    /// The options of the container annotation listing the source enum.
+    pub options: ContainerOptions,
+    /// The variants declared in the container annotation, which must all be converted.
+    pub declared_variants: Option<Vec<VariantIdent>>,
+    /// The arms converting the source variants.
+    pub variants_mapping: VariantsMapping,
+}
+
+/// A variant of the annotated enum.
+pub struct TargetVariant {
+    /// The definition of the variant.
+    pub variant: Variant,
+    /// The options of the annotated fields.
+    pub fields_options: IndexMap<FieldRef, FieldOptions>,
+}
+
+/// For each source variant, the arms converting it in the order they are matched.
+/// All the arms but the last one have a guard.
+pub struct VariantsMapping(pub IndexMap<VariantIdent, Vec<VariantArm>>);
+
+/// The conversion of a source variant, or of the values of a source variant matching the guard
+/// or the pattern, into a target variant.
+pub struct VariantArm {
+    /// The guard restricting the converted values.
+    pub guard: Option<Expr>,
+    /// The span of the annotation, for the errors.
+    pub span: Span,
+    /// The variants of other enums wrapped in the source variant, from the outermost to the
+    /// innermost one, which is the one converted by `variant_mapping`.
+    pub inner_variants: Vec<(ContainerIdent, VariantIdent)>,
+    /// How the fields are converted.
+    pub variant_mapping: VariantMapping,
+}
+
+/// The target variant of an arm, with the source field converted into each target field.
+pub enum VariantMapping {
+    /// A unit source variant converted into a unit target variant.
+    UnitToUnit {
+        /// The target variant.
+        target_variant: VariantIdent,
+    },
+    /// A tuple source variant converted into a tuple target variant.
+    TupleToTuple {
+        /// The target variant.
+        target_variant: VariantIdent,
+        /// The source position of each target position.
+        fields_mapping: IndexMap<usize, usize>,
+    },
+    /// A tuple source variant converted into a struct target variant.
+    TupleToStruct {
+        /// The target variant.
+        target_variant: VariantIdent,
+        /// The source position of each target field.
+        fields_mapping: IndexMap<FieldIdent, usize>,
+    },
+    /// A struct source variant converted into a struct target variant.
+    StructToStruct {
+        /// The target variant.
+        target_variant: VariantIdent,
+        /// The source field of each target field, the missing ones having the same name.
+        fields_mapping: IndexMap<FieldIdent, FieldIdent>,
+    },
+    /// A struct source variant converted into a tuple target variant.
+    StructToTuple {
+        /// The target variant.
+        target_variant: VariantIdent,
+        /// The source field of each target position.
+        fields_mapping: IndexMap<usize, FieldIdent>,
+    },
+    /// The fields of the source variant are matched by a pattern, they are not converted.
+    PatternToUnit {
+        /// The target variant.
+        target_variant: VariantIdent,
+        /// The pattern, with its delimiters.
+        payload: Group,
+    },
+}
+
+impl VariantMapping {
+    /// The target variant of the arm.
+    pub fn target_variant(&self) -> &VariantIdent {
+        match self {
+            VariantMapping::UnitToUnit { target_variant } => target_variant,
+            VariantMapping::TupleToTuple { target_variant, .. } => target_variant,
+            VariantMapping::TupleToStruct { target_variant, .. } => target_variant,
+            VariantMapping::StructToStruct { target_variant, .. } => target_variant,
+            VariantMapping::StructToTuple { target_variant, .. } => target_variant,
+            VariantMapping::PatternToUnit { target_variant, .. } => target_variant,
+        }
+    }
+}
+
+impl VariantArm {
+    /// Whether the arm only matches some values of the source variant.
+    pub fn is_refined(&self) -> bool {
+        self.guard.is_some() || self.has_pattern()
+    }
+
+    /// Whether the arm matches the fields of the source variant with a pattern, including the
+    /// fields bound for the guard of a unit target variant.
+    pub fn has_pattern(&self) -> bool {
+        !self.inner_variants.is_empty()
+            || matches!(self.variant_mapping, VariantMapping::PatternToUnit { .. })
+            || (self.guard.is_some()
+                && matches!(self.variant_mapping, VariantMapping::UnitToUnit { .. }))
+    }
+}
+
+impl EnumFromMapping {
+    /// Fails if some annotations only make sense for EnumFromAsync.
+    pub fn check_sync(self) -> syn::Result<Self> {
+        let mut errors = Errors::default();
+        for (_, SourceEnum { options, .. }) in sorted_by_name(&self.source_enums) {
+            if let Some(error) = &options.error {
+                errors.push(syn::Error::new_spanned(
+                    error,
+                    "an error type can only be specified when deriving EnumFromAsync",
+                ));
+            }
+        }
+        for (_, TargetVariant { fields_options, .. }) in sorted_by_name(&self.target_variants) {
+            let fields_options = fields_options.iter().collect::<BTreeMap<_, _>>();
+            for (_, options) in fields_options {
+                if let Some(with) = &options.with {
+                    errors.push(syn::Error::new_spanned(
+                        with,
+                        "an async conversion function can only be specified when deriving EnumFromAsync",
+                    ));
+                }
+            }
+        }
+        errors.finish().map(|()| self)
+    }
+
+    /// Fails if some annotations only make sense for EnumFrom.
+    pub fn check_async(self) -> syn::Result<Self> {
+        let mut errors = Errors::default();
+        for IntegerSource { integer_type, .. } in &self.integer_sources {
+            errors.push(syn::Error::new_spanned(
+                integer_type,
+                "the conversion from an integer can only be derived with EnumFrom",
+            ));
+        }
+        errors.finish().map(|()| self)
+    }
+}
+
+impl TryFrom<ParsedEnumFrom> for EnumFromMapping {
+    type Error = syn::Error;
+
+    fn try_from(
+        ParsedEnumFrom {
+            target_enum,
+            container_annotations,
+            integer_sources,
+            variants_annotations,
+        }: ParsedEnumFrom,
+    ) -> Result<Self, Self::Error> {
+        let single_source_enum = match &container_annotations[..] {
+            [] if integer_sources.is_empty() => Err(syn::Error::new(
+                Span::call_site(),
+                "enum_from attribute with source enum names is required",
+            ))?,
+            [source_enum] => Some(source_enum.source_enum.clone()),
+            _ => None,
+        };
+
+        let mut errors = Errors::default();
+        let mut target_variants: IndexMap<VariantIdent, TargetVariant> = IndexMap::new();
+
+        let mut source_enums = container_annotations
+            .into_iter()
+            .map(
+                |ContainerAnnotation {
+                     source_enum,
+                     declared_variants,
+                     options,
+                 }| {
+                    (
+                        source_enum,
+                        SourceEnum {
+                            options,
+                            declared_variants,
+                            variants_mapping: VariantsMapping(IndexMap::new()),
+                        },
+                    )
+                },
+            )
+            .collect::<IndexMap<_, _>>();
+
+        let mut integer_sources = integer_sources
+            .into_iter()
+            .map(|integer_type| IntegerSource {
+                integer_type,
+                arms: Vec::new(),
+            })
+            .collect::<Vec<_>>();
+        let variants_discriminants =
+            discriminants(variants_annotations.iter().map(|(variant, _)| variant));
+        let mut discriminants = Vec::new();
+
+        for ((target_variant, mut variant_annotations), discriminant) in
+            variants_annotations.into_iter().zip(variants_discriminants)
+        {
+            let fields_options = variant_annotations
+                .fields_annotations
+                .iter()
+                .map(|(target_field, field_annotations)| {
+                    (target_field.clone(), field_annotations.options.clone())
+                })
+                .collect();
+
+            let (integer_annotations, variant_annotations_list): (Vec<_>, Vec<_>) =
+                variant_annotations
+                    .variant_annotations
+                    .into_iter()
+                    .partition(|annotation| {
+                        matches!(annotation, VariantAnnotation::Integer { .. })
+                    });
+            let (skip_annotations, mut mapping_annotations): (Vec<_>, Vec<_>) =
+                variant_annotations_list
+                    .into_iter()
+                    .partition(|annotation| matches!(annotation, VariantAnnotation::Skip { .. }));
+            if matches!(target_variant.fields, Fields::Unit) && skip_annotations.is_empty() {
+                discriminants.push((
+                    VariantIdent(target_variant.ident.clone()),
+                    discriminant.clone(),
+                ));
+            }
+            errors.ok(add_integer_arms(
+                &mut integer_sources,
+                &target_variant,
+                integer_annotations,
+                discriminant,
+                !skip_annotations.is_empty(),
+            ));
+            if let Some(VariantAnnotation::Skip { span }) = skip_annotations.first() {
+                if !mapping_annotations.is_empty() {
+                    errors.push(syn::Error::new(
+                        *span,
+                        "a skipped variant cannot be converted from a source enum",
+                    ));
+                    mapping_annotations.clear();
+                }
+            } else if mapping_annotations.is_empty() {
+                // Without annotation, the variant is converted from the sources with `all`.
+                mapping_annotations = source_enums
+                    .iter()
+                    .filter(|(_, SourceEnum { options, .. })| {
+                        options.all && !options.by_discriminant
+                    })
+                    .map(|(source_enum, _)| VariantAnnotation::EnumOnly {
+                        span: target_variant.ident.span(),
+                        enum_ident: source_enum.clone(),
+                        guard: None,
+                    })
+                    .collect();
+            }
+
+            let mut fields_sources = Vec::new();
+            for variant_annotation in mapping_annotations {
+                fields_sources.extend(errors.ok(add_variant_arm(
+                    &mut source_enums,
+                    single_source_enum.as_ref(),
+                    &target_variant,
+                    &mut variant_annotations.fields_annotations,
+                    variant_annotation,
+                )));
+            }
+
+            errors.ok(check_unused_fields_annotations(
+                &source_enums,
+                &fields_sources,
+                variant_annotations.fields_annotations,
+            ));
+            target_variants.insert(
+                VariantIdent(target_variant.ident.clone()),
+                TargetVariant {
+                    variant: target_variant,
+                    fields_options,
+                },
+            );
+        }
+
+        let mut sorted_source_enums = source_enums.iter_mut().collect::<Vec<_>>();
+        sorted_source_enums.sort_by_key(|(source_enum, _)| source_enum.to_string());
+        for (
+            source_enum,
+            SourceEnum {
+                options,
+                declared_variants,
+                variants_mapping,
+            },
+        ) in sorted_source_enums
+        {
+            if let Some(declared_variants) = declared_variants {
+                errors.ok(check_declared_variants(
+                    source_enum,
+                    declared_variants,
+                    &target_enum,
+                    variants_mapping,
+                    options.by_discriminant,
+                ));
+            } else if options.by_discriminant {
+                errors.push(syn::Error::new(
+                    source_enum.0.span(),
+                    format!(
+                        "the variants of `{source_enum}` must be declared to be matched by discriminant, for example #[enum_from({source_enum} {{ A, B }}, by_discriminant)]"
+                    ),
+                ));
+            }
+            let mut variants_mapping = variants_mapping.0.iter_mut().collect::<Vec<_>>();
+            variants_mapping.sort_by_key(|(source_variant, _)| source_variant.to_string());
+            for (source_variant, variant_arms) in variants_mapping {
+                errors.ok(order_variant_arms(
+                    source_enum,
+                    source_variant,
+                    variant_arms,
+                ));
+            }
+        }
+        errors.finish()?;
+
+        Ok(EnumFromMapping {
+            source_enums,
+            target_enum,
+            target_variants,
+            integer_sources,
+            discriminants,
+        })
+    }
+}
+
+/// Adds the integers converted into the target variant when it is a unit variant: the patterns of
+/// its annotations if any, otherwise its discriminant unless it is skipped.
+fn add_integer_arms(
+    integer_sources: &mut [IntegerSource],
+    target_variant: &Variant,
+    integer_annotations: Vec<VariantAnnotation>,
+    discriminant: TokenStream,
+    skipped: bool,
+) -> syn::Result<()> {
+    let mut errors = Errors::default();
+    let mut patterns: IndexMap<Ident, Vec<Pat>> = IndexMap::new();
+    for annotation in integer_annotations {
+        let VariantAnnotation::Integer {
+            span,
+            integer_type,
+            pattern,
+        } = annotation
+        else {
+            continue;
+        };
+        if !integer_sources
+            .iter()
+            .any(|integer_source| integer_source.integer_type == integer_type)
+        {
+            errors.push(syn::Error::new(
+                span,
+                did_you_mean(
+                    format!(
+                        "integer type `{integer_type}` is not specified in this enum's #[enum_from] annotation"
+                    ),
+                    &integer_type,
+                    integer_sources
+                        .iter()
+                        .map(|integer_source| &integer_source.integer_type),
+                ),
+            ));
+        } else if !matches!(target_variant.fields, Fields::Unit) {
+            errors.push(syn::Error::new(
+                span,
+                "only a unit variant can be converted from an integer",
+            ));
+        } else {
+            patterns.entry(integer_type).or_default().push(pattern);
+        }
+    }
+    errors.finish()?;
+
+    if !matches!(target_variant.fields, Fields::Unit) {
+        return Ok(());
+    }
+    for IntegerSource { integer_type, arms } in integer_sources {
+        let pattern = match patterns.shift_remove(integer_type) {
+            Some(patterns) => IntegerPattern::Patterns(patterns),
+            None if skipped => continue,
+            None => IntegerPattern::Discriminant(discriminant.clone()),
+        };
+        arms.push((VariantIdent(target_variant.ident.clone()), pattern));
+    }
+    Ok(())
+}
+
+/// Adds the arm converting the source variant of the annotation into the target variant.
+/// Returns the enum and variant the fields are converted from.
+fn add_variant_arm(
+    source_enums: &mut IndexMap<ContainerIdent, SourceEnum>,
+    single_source_enum: Option<&ContainerIdent>,
+    target_variant: &Variant,
+    fields_annotations: &mut HashMap<FieldRef, FieldAnnotations>,
+    variant_annotation: VariantAnnotation,
+) -> syn::Result<(ContainerIdent, VariantIdent)> {
+    let MappingSource {
+        source_enum,
+        source_variant,
+        inner_variants,
+        payload,
+        guard,
+        span,
+    } = get_mapping_source(single_source_enum, variant_annotation)?;
+
+    let Some(SourceEnum {
+        options,
+        variants_mapping: VariantsMapping(variants_mapping),
+        ..
+    }) = source_enums.get_mut(&source_enum)
+    else {
+        return Err(syn::Error::new(
+            span,
+            did_you_mean(
+                format!(
+                    "source enum `{source_enum}` is not specified in this enum's #[enum_from] annotation"
+                ),
+                &source_enum,
+                source_enums.keys(),
+            ),
+        ));
+    };
+    if options.by_discriminant {
+        return Err(syn::Error::new(
+            span,
+            format!(
+                "the variants of `{source_enum}` are matched by discriminant, they cannot be mapped by an annotation"
+            ),
+        ));
+    }
+    let source_variant = match source_variant {
+        Some(source_variant) => source_variant,
+        None => VariantIdent(options.renaming.apply(&target_variant.ident)?),
+    };
+
+    // The fields are converted from the innermost variant.
+    let (fields_source_enum, fields_source_variant) = inner_variants
+        .last()
+        .map(|(inner_enum, inner_variant)| (inner_enum, inner_variant))
+        .unwrap_or((&source_enum, &source_variant));
+    let fields_annotations = extract_fields_annotations(
+        fields_annotations,
+        fields_source_enum,
+        fields_source_variant,
+    )?;
+    let fields_source = (fields_source_enum.clone(), fields_source_variant.clone());
+    let fields = &target_variant.fields;
+    let target_variant = VariantIdent(target_variant.ident.clone());
+    let variant_mapping = match payload {
+        Some(payload) => compute_pattern_variant_mapping(payload, fields, target_variant)?,
+        None => compute_variant_mapping(
+            fields_source_enum,
+            fields_source_variant,
+            fields_annotations,
+            &options.field_renaming,
+            fields,
+            target_variant,
+        )?,
+    };
+
+    variants_mapping
+        .entry(source_variant)
+        .or_default()
+        .push(VariantArm {
+            guard,
+            span,
+            inner_variants,
+            variant_mapping,
+        });
+    Ok(fields_source)
+}
+
+fn compute_variant_mapping(
+    source_enum: &ContainerIdent,
+    source_variant: &VariantIdent,
+    fields_annotations: BTreeMap<FieldRef, FieldAnnotation>,
+    field_renaming: &Renaming,
+    fields: &Fields,
+    target_variant: VariantIdent,
+) -> syn::Result<VariantMapping> {
+    // The fields annotations are keyed by the fields of the target variant, so a unit variant
+    // has none.
+    match (
+        fields,
+        fields_annotations
+            .first_key_value()
+            .map(|(_, field_annotation)| &field_annotation.source_field),
+    ) {
+        (Fields::Unit, _) => Ok(VariantMapping::UnitToUnit { target_variant }),
+        (Fields::Unnamed(fields), None)
+        | (Fields::Unnamed(fields), Some(FieldRef::FieldPos(_))) => {
+            compute_tuple_to_tuple_variant_mapping(fields_annotations, fields, target_variant)
+        }
+        (Fields::Named(fields), None) | (Fields::Named(fields), Some(FieldRef::FieldIdent(_))) => {
+            compute_struct_to_struct_variant_mapping(
+                fields_annotations,
+                field_renaming,
+                fields,
+                target_variant,
+            )
+        }
+        (Fields::Unnamed(fields), Some(FieldRef::FieldIdent(_))) => {
+            compute_struct_to_tuple_variant_mapping(
+                source_enum,
+                source_variant,
+                fields_annotations,
+                fields,
+                target_variant,
+            )
+        }
+        (Fields::Named(fields), Some(FieldRef::FieldPos(_))) => {
+            compute_tuple_to_struct_variant_mapping(
+                source_enum,
+                source_variant,
+                fields_annotations,
+                fields,
+                target_variant,
+            )
+        }
+    }
+}
+
+fn compute_pattern_variant_mapping(
+    payload: Group,
+    fields: &Fields,
+    target_variant: VariantIdent,
+) -> syn::Result<VariantMapping> {
+    match fields {
+        Fields::Unit => Ok(VariantMapping::PatternToUnit {
+            target_variant,
+            payload,
+        }),
+        Fields::Named(_) | Fields::Unnamed(_) => Err(syn::Error::new(
+            payload.span(),
+            "a source variant matched with a payload pattern can only be converted into a unit variant",
+        )),
+    }
+}
+
+fn compute_tuple_to_tuple_variant_mapping(
+    mut fields_annotations: BTreeMap<FieldRef, FieldAnnotation>,
+    fields: &FieldsUnnamed,
+    target_variant: VariantIdent,
+) -> syn::Result<VariantMapping> {
+    let mut fields_mapping = IndexMap::new();
+    for target_pos in 0..fields.unnamed.len() {
+        match fields_annotations.remove(&FieldRef::FieldPos(target_pos)) {
+            None => {}
+            Some(FieldAnnotation {
+                source_field: FieldRef::FieldPos(source_pos),
+                ..
+            }) => {
+                fields_mapping.insert(target_pos, source_pos);
+            }
+            Some(FieldAnnotation { field_span, .. }) => Err(syn::Error::new(
+                field_span,
+                "Unexpected mapping to named field while another field mapped to a positional field.",
+            ))?,
+        }
+    }
+
+    Ok(VariantMapping::TupleToTuple {
+        target_variant,
+        fields_mapping,
+    })
+}
+
+fn compute_struct_to_struct_variant_mapping(
+    mut fields_annotations: BTreeMap<FieldRef, FieldAnnotation>,
+    field_renaming: &Renaming,
+    fields: &FieldsNamed,
+    target_variant: VariantIdent,
+) -> syn::Result<VariantMapping> {
+    let mut fields_mapping = IndexMap::new();
+    for field in fields.named.iter() {
+        let target_field = FieldIdent(field.ident.clone().expect("Named fields have idents"));
+        match fields_annotations.remove(&FieldRef::FieldIdent(target_field.clone())) {
+            None => {}
+            Some(FieldAnnotation {
+                source_field: FieldRef::FieldIdent(source_field),
+                ..
+            }) => {
+                fields_mapping.insert(target_field, source_field);
+            }
+            Some(FieldAnnotation { field_span, .. }) => Err(syn::Error::new(
+                field_span,
+                "Unexpected mapping to positional field while another field mapped to a named field.",
+            ))?,
+        }
+    }
+    for field in fields.named.iter() {
+        let target_field = FieldIdent(field.ident.clone().expect("Named fields have idents"));
+        if let Entry::Vacant(entry) = fields_mapping.entry(target_field) {
+            let source_field = FieldIdent(field_renaming.apply(&entry.key().0)?);
+            entry.insert(source_field);
+        }
+    }
+
+    Ok(VariantMapping::StructToStruct {
+        target_variant,
+        fields_mapping,
+    })
+}
+
+fn compute_struct_to_tuple_variant_mapping(
+    source_enum: &ContainerIdent,
+    source_variant: &VariantIdent,
+    mut fields_annotations: BTreeMap<FieldRef, FieldAnnotation>,
+    fields: &FieldsUnnamed,
+    target_variant: VariantIdent,
+) -> syn::Result<VariantMapping> {
+    let mut fields_mapping = IndexMap::new();
+    for (target_pos, field) in fields.unnamed.iter().enumerate() {
+        match fields_annotations.remove(&FieldRef::FieldPos(target_pos)) {
+            None => Err(syn::Error::new(
+                field.span(),
+                format!(
+                    "Missing required mapping to named field for {source_enum}::{source_variant}"
+                ),
+            ))?,
+            Some(FieldAnnotation {
+                source_field: FieldRef::FieldIdent(source_ident),
+                ..
+            }) => {
+                fields_mapping.insert(target_pos, source_ident);
+            }
+            Some(FieldAnnotation { field_span, .. }) => Err(syn::Error::new(
+                field_span,
+                "Unexpected mapping to positional field while another field mapped to a named field.",
+            ))?,
+        }
+    }
+
+    Ok(VariantMapping::StructToTuple {
+        target_variant,
+        fields_mapping,
+    })
+}
+
+fn compute_tuple_to_struct_variant_mapping(
+    source_enum: &ContainerIdent,
+    source_variant: &VariantIdent,
+    mut fields_annotations: BTreeMap<FieldRef, FieldAnnotation>,
+    fields: &FieldsNamed,
+    target_variant: VariantIdent,
+) -> syn::Result<VariantMapping> {
+    let mut fields_mapping = IndexMap::new();
+    for field in fields.named.iter() {
+        let target_ident = FieldIdent(field.ident.clone().expect("Named fields have idents"));
+        match fields_annotations.remove(&FieldRef::FieldIdent(target_ident.clone())) {
+            None => Err(syn::Error::new(
+                field.span(),
+                format!(
+                    "Missing required mapping to named field for {source_enum}::{source_variant}"
+                ),
+            ))?,
+            Some(FieldAnnotation {
+                source_field: FieldRef::FieldPos(source_pos),
+                ..
+            }) => {
+                fields_mapping.insert(target_ident, source_pos);
+            }
+            Some(FieldAnnotation { field_span, .. }) => Err(syn::Error::new(
+                field_span,
+                "Unexpected mapping to named field while another field mapped to a positional field.",
+            ))?,
+        }
+    }
+
+    Ok(VariantMapping::TupleToStruct {
+        target_variant,
+        fields_mapping,
+    })
+}
+
+/// Fails on the fields annotations which do not match any of the `fields_sources` the variant
+/// is converted from.
+fn check_unused_fields_annotations(
+    source_enums: &IndexMap<ContainerIdent, SourceEnum>,
+    fields_sources: &[(ContainerIdent, VariantIdent)],
+    fields_annotations: HashMap<FieldRef, FieldAnnotations>,
+) -> syn::Result<()> {
+    let mut errors = Errors::default();
+    let fields_annotations = fields_annotations.into_iter().collect::<BTreeMap<_, _>>();
+    for (_, field_annotations) in fields_annotations {
+        for field_annotation in field_annotations.fields_annotations {
+            if source_enums.contains_key(&field_annotation.source_enum) {
+                let source_variants = fields_sources
+                    .iter()
+                    .filter(|(source_enum, _)| *source_enum == field_annotation.source_enum)
+                    .map(|(_, source_variant)| source_variant);
+                errors.push(syn::Error::new(
+                    field_annotation.variant_span,
+                    did_you_mean(
+                        "Field mapping for unexpected enum and variant combination",
+                        &field_annotation.source_variant,
+                        source_variants,
+                    ),
+                ))
+            } else {
+                errors.push(syn::Error::new(
+                    field_annotation.enum_span,
+                    did_you_mean(
+                        "Field mapping for unknown enum",
+                        &field_annotation.source_enum,
+                        source_enums.keys(),
+                    ),
+                ))
+            }
+        }
+    }
+    errors.finish()
+}
+
+fn extract_fields_annotations(
+    fields_annotations: &mut HashMap<FieldRef, FieldAnnotations>,
+    source_enum: &ContainerIdent,
+    source_variant: &VariantIdent,
+) -> syn::Result<BTreeMap<FieldRef, FieldAnnotation>> {
+    let fields_annotations = fields_annotations.iter_mut().collect::<BTreeMap<_, _>>();
+    Ok(fields_annotations
+        .into_iter()
+        .filter_map(|(target_field, field_annotations)| {
+            let mut annotations = field_annotations
+                .fields_annotations
+                .extract_if(.., |field_annotation| {
+                    field_annotation.source_enum == *source_enum
+                        && field_annotation.source_variant == *source_variant
+                })
+                .collect::<Vec<_>>();
+            let annotation = annotations.pop();
+            if annotations.pop().is_some() {
+                Some(Err(syn::Error::new(
+                    field_annotations.field_span,
+                    format!("Multiple mapping found for source enum `{source_enum}`"),
+                )))
+            } else {
+                annotation.map(|annotation| Ok((target_field.clone(), annotation)))
+            }
+        })
+        .collect_errors()?
+        .into_iter()
+        .collect())
+}
+
+/// Moves the refined arms, those with a guard or a payload pattern, before the unrefined one
+/// which must be unique.
+fn order_variant_arms(
+    source_enum: &ContainerIdent,
+    source_variant: &VariantIdent,
+    variant_arms: &mut [VariantArm],
+) -> syn::Result<()> {
+    // The sort is stable so the refined arms are matched in declaration order.
+    variant_arms.sort_by_key(|variant_arm| !variant_arm.is_refined());
+    match &variant_arms[..] {
+        // Payload patterns may cover all the values of the source variant, which is checked by
+        // the compiler.
+        [.., last]
+            if last.is_refined()
+                && !variant_arms
+                    .iter()
+                    .any(|variant_arm| variant_arm.has_pattern()) =>
+        {
+            Err(syn::Error::new(
+                last.span,
+                format!(
+                    "`{source_enum}::{source_variant}` is only mapped with guards, it must also be mapped without guard"
+                ),
+            ))
+        }
+        [.., previous, last] if !previous.is_refined() => Err(syn::Error::new(
+            last.span,
+            format!("`{source_enum}::{source_variant}` is mapped multiple times without guard"),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Fails on the declared source variants which are not converted, and on the converted source
+/// variants which are not declared.
+fn check_declared_variants(
+    source_enum: &ContainerIdent,
+    declared_variants: &[VariantIdent],
+    target_enum: &ContainerIdent,
+    variants_mapping: &VariantsMapping,
+    by_discriminant: bool,
+) -> syn::Result<()> {
+    let mut errors = Errors::default();
+    let mut declared = HashSet::new();
+    for declared_variant in declared_variants {
+        if !declared.insert(declared_variant) {
+            errors.push(syn::Error::new(
+                declared_variant.0.span(),
+                format!("`{source_enum}::{declared_variant}` is declared multiple times"),
+            ));
+        } else if !by_discriminant && !variants_mapping.0.contains_key(declared_variant) {
+            errors.push(syn::Error::new(
+                declared_variant.0.span(),
+                format!(
+                    "`{source_enum}::{declared_variant}` is not converted into any variant of `{target_enum}`"
+                ),
+            ));
+        }
+    }
+    for (source_variant, variant_arms) in sorted_by_name(&variants_mapping.0) {
+        if !declared.contains(source_variant) {
+            errors.push(syn::Error::new(
+                variant_arms[0].span,
+                did_you_mean(
+                    format!(
+                        "`{source_enum}::{source_variant}` is not declared in the variants of `{source_enum}`"
+                    ),
+                    source_variant,
+                    declared_variants,
+                ),
+            ));
+        }
+    }
+    errors.finish()
+}
+
+/// Where a mapping converts from, as specified by a variant annotation.
+struct MappingSource {
+    source_enum: ContainerIdent,
+    /// `None` when the source variant is named after the target variant.
+    source_variant: Option<VariantIdent>,
+    inner_variants: Vec<(ContainerIdent, VariantIdent)>,
+    payload: Option<Group>,
+    guard: Option<Expr>,
+    span: Span,
+}
+
+/// Returns the source enum and variant for the given variant annotation.
+fn get_mapping_source(
+    single_source_enum: Option<&ContainerIdent>,
+    variant_annotation: VariantAnnotation,
+) -> syn::Result<MappingSource> {
+    match variant_annotation {
+        VariantAnnotation::Skip { .. } => unreachable!("skipped variants have no mapping"),
+        VariantAnnotation::Integer { .. } => unreachable!("integers have no source enum"),
+        VariantAnnotation::Nothing { span } => {
+            if let Some(source_enum) = single_source_enum {
+                Ok(MappingSource {
+                    source_enum: source_enum.clone(),
+                    source_variant: None,
+                    inner_variants: Vec::new(),
+                    payload: None,
+                    guard: None,
+                    span,
+                })
+            } else {
+                Err(syn::Error::new(
+                    span,
+                    "When multiple source enums are specified, each variant must specify from which enum to convert with #[enum_from(Enum)] or #[enum_from(Enum::Variant)]",
+                ))
+            }
+        }
+        VariantAnnotation::EnumOnly {
+            span,
+            enum_ident,
+            guard,
+        } => Ok(MappingSource {
+            source_enum: enum_ident,
+            source_variant: None,
+            inner_variants: Vec::new(),
+            payload: None,
+            guard,
+            span,
+        }),
+        VariantAnnotation::EnumVariant {
+            span,
+            enum_ident,
+            variant_ident,
+            mut payload,
+            guard,
+        } => {
+            let mut inner_variants = Vec::new();
+            while let Some(Payload::Nested {
+                enum_ident,
+                variant_ident,
+                payload: inner_payload,
+            }) = payload
+            {
+                inner_variants.push((enum_ident, variant_ident));
+                payload = inner_payload.map(|inner_payload| *inner_payload);
+            }
+            Ok(MappingSource {
+                source_enum: enum_ident,
+                source_variant: Some(variant_ident),
+                inner_variants,
+                payload: payload.map(|payload| match payload {
+                    Payload::Pattern(pattern) => pattern,
+                    Payload::Nested { .. } => unreachable!("nested payloads have been unwrapped"),
+                }),
+                guard,
+                span,
+            })
+        }
+    }
+}
@@ -0,0 +1,744 @@
+//! The parsing of the `#[enum_from]` annotations of the enum and of its variants and fields.
+use std::collections::HashMap;
+
+use proc_macro2::{Delimiter, Group, Span, TokenStream};
+use syn::{
+    Attribute, Data, DataEnum, DeriveInput, Expr, Field, Ident, LitInt, LitStr, Meta, Pat, Path,
+    Token, Type, Variant,
+    parse::{Parse, ParseStream, Parser},
+    punctuated::Punctuated,
+    spanned::Spanned,
+    token,
+};
+
+use crate::{
+    discriminants::is_integer_type,
+    errors::{self, CollectErrors},
+    idents::{ContainerIdent, FieldIdent, FieldRef, VariantIdent},
+    renaming::{FIELD_RENAMING_OPTIONS, RENAMING_OPTIONS, Renaming},
+};
+
+/// A "dumb" parser of the EnumFrom annotations
+/// There is no check of consistency between annotations here.
+pub struct ParsedEnumFrom {
+    /// The annotated enum.
+    pub target_enum: ContainerIdent,
+    /// The source enums listed in the container annotations, in their order.
+    pub container_annotations: Vec<ContainerAnnotation>,
+    /// The integer types listed in the container annotations, for example `u8` in
+    /// `#[enum_from(u8)]`.
+    pub integer_sources: Vec<Ident>,
+    /// The variants with their annotations, in declaration order.
+    pub variants_annotations: Vec<(Variant, VariantAnnotations)>,
+}
+
+impl ParsedEnumFrom {
+    /// Parses the annotations of the enum given to the derive macro.
+    pub fn parse(input: TokenStream) -> syn::Result<ParsedEnumFrom> {
+        let derive_input: DeriveInput = syn::parse2(input)?;
+
+        let data_enum = match derive_input.data {
+            Data::Enum(data) => data,
+            Data::Struct(_) | Data::Union(_) => Err(syn::Error::new(
+                Span::call_site(),
+                "EnumFrom can only be derived for enums",
+            ))?,
+        };
+
+        let target_enum = ContainerIdent(derive_input.ident);
+        let ((container_annotations, integer_sources), variants_annotations) = errors::join(
+            extract_container_annotations(&derive_input.attrs),
+            extract_variants_annotations(data_enum),
+        )?;
+
+        Ok(ParsedEnumFrom {
+            target_enum,
+            container_annotations,
+            integer_sources,
+            variants_annotations,
+        })
+    }
+}
+
+/// A source enum listed in a container annotation, for example `Source` in
+/// `#[enum_from(Source, all)]`.
+pub struct ContainerAnnotation {
+    /// The source enum.
+    pub source_enum: ContainerIdent,
+    /// The variants of the source enum listed in the annotation, for example
+    /// `#[enum_from(Source { A, B })]`.
+    pub declared_variants: Option<Vec<VariantIdent>>,
+    /// The options of the annotation.
+    pub options: ContainerOptions,
+}
+
+/// Options applying to all the source enums listed in the same annotation.
+#[derive(Clone, Default)]
+pub struct ContainerOptions {
+    /// Error type of the `FromAsync` implementation, only used by EnumFromAsync.
+    pub error: Option<Type>,
+    /// Whether the fields are moved without conversion by default.
+    pub as_is: bool,
+    /// Whether the annotations are checked against the registered definition of the enums.
+    pub registered: bool,
+    /// Whether the target variants without annotation are converted from the source variants of
+    /// the same name.
+    pub all: bool,
+    /// Whether the declared source variants are converted into the unit target variants with the
+    /// same discriminant, instead of the same name.
+    pub by_discriminant: bool,
+    /// Whether the generated code is printed while compiling.
+    pub debug: bool,
+    /// Whether the conversions are described by an `EnumMapping` implementation.
+    pub mapping: bool,
+    /// How the source variants are named after the target variants when not specified.
+    pub(crate) renaming: Renaming,
+    /// How the source fields are named after the target fields when not specified.
+    pub(crate) field_renaming: Renaming,
+}
+
+enum ContainerItem {
+    SourceEnum(Ident, Option<Vec<VariantIdent>>),
+    Integer(Ident),
+    Error(Type),
+    AsIs(Ident),
+    Registered(Ident),
+    All(Ident),
+    ByDiscriminant(Ident),
+    Debug(Ident),
+    Mapping(Ident),
+    Renaming(Ident, LitStr),
+}
+
+impl Parse for ContainerItem {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ident: Ident = input.parse()?;
+        if !input.peek(Token![=]) {
+            if ident == "as_is" {
+                return Ok(Self::AsIs(ident));
+            }
+            if ident == "registered" {
+                return Ok(Self::Registered(ident));
+            }
+            if ident == "all" {
+                return Ok(Self::All(ident));
+            }
+            if ident == "by_discriminant" {
+                return Ok(Self::ByDiscriminant(ident));
+            }
+            if ident == "debug" {
+                return Ok(Self::Debug(ident));
+            }
+            if ident == "mapping" {
+                return Ok(Self::Mapping(ident));
+            }
+            if is_integer_type(&ident) {
+                return Ok(Self::Integer(ident));
+            }
+            let declared_variants = if input.peek(token::Brace) {
+                let content;
+                syn::braced!(content in input);
+                let variants = Punctuated::<Ident, Token![,]>::parse_terminated(&content)?;
+                Some(variants.into_iter().map(VariantIdent).collect())
+            } else {
+                None
+            };
+            return Ok(Self::SourceEnum(ident, declared_variants));
+        }
+        input.parse::<Token![=]>()?;
+        if ident == "error" {
+            Ok(Self::Error(input.parse()?))
+        } else if RENAMING_OPTIONS
+            .iter()
+            .chain(FIELD_RENAMING_OPTIONS.iter())
+            .any(|option| ident == option)
+        {
+            Ok(Self::Renaming(ident, input.parse()?))
+        } else {
+            let expected = [
+                "error",
+                "as_is",
+                "all",
+                "by_discriminant",
+                "debug",
+                "mapping",
+                "registered",
+            ]
+            .iter()
+            .chain(RENAMING_OPTIONS.iter())
+            .chain(FIELD_RENAMING_OPTIONS.iter())
+            .map(|option| format!("`{option}`"))
+            .collect::<Vec<_>>()
+            .join(", ");
+            Err(syn::Error::new(
+                ident.span(),
+                format!("unknown option `{ident}`, expected one of {expected}"),
+            ))
+        }
+    }
+}
+
+/// The annotations of a variant and of its fields.
+pub struct VariantAnnotations {
+    /// The items of the variant annotations, in their order.
+    pub variant_annotations: Vec<VariantAnnotation>,
+    /// The annotations of each field.
+    pub fields_annotations: HashMap<FieldRef, FieldAnnotations>,
+}
+
+/// An item of a variant annotation.
+pub enum VariantAnnotation {
+    /// `#[enum_from]`, the variant is converted from the variant of the same name of the single
+    /// source enum.
+    Nothing {
+        /// The span of the annotation.
+        span: Span,
+    },
+    /// The variant is not converted from the source enums with the `all` option.
+    Skip {
+        /// The span of the `skip` keyword.
+        span: Span,
+    },
+    /// `Source`, the variant is converted from the variant of the same name of the source enum.
+    EnumOnly {
+        /// The span of the item.
+        span: Span,
+        /// The source enum.
+        enum_ident: ContainerIdent,
+        /// The guard after `if`, restricting the converted values.
+        guard: Option<Expr>,
+    },
+    /// `Source::Variant`, the variant is converted from this source variant.
+    EnumVariant {
+        /// The span of the item.
+        span: Span,
+        /// The source enum.
+        enum_ident: ContainerIdent,
+        /// The source variant.
+        variant_ident: VariantIdent,
+        /// What matches the fields of the source variant, if any.
+        payload: Option<Payload>,
+        /// The guard after `if`, restricting the converted values.
+        guard: Option<Expr>,
+    },
+    /// The integers converted into the variant instead of its discriminant, for example
+    /// `#[enum_from(u8 = 0x10 | 0x11)]`.
+    Integer {
+        /// The span of the item.
+        span: Span,
+        /// The integer type.
+        integer_type: Ident,
+        /// The pattern of the integers.
+        pattern: Pat,
+    },
+}
+
+/// What follows `Enum::Variant` in a variant annotation to match the fields of the variant.
+pub enum Payload {
+    /// A pattern matching the fields, for example `(404)`.
+    Pattern(Group),
+    /// A variant of another enum wrapped in a single field tuple variant, for example
+    /// `(Kind::NotFound)`.
+    Nested {
+        /// The wrapped enum.
+        enum_ident: ContainerIdent,
+        /// The wrapped variant.
+        variant_ident: VariantIdent,
+        /// What matches the fields of the wrapped variant, if any.
+        payload: Option<Box<Payload>>,
+    },
+}
+
+impl Payload {
+    fn parse_optional(input: ParseStream) -> syn::Result<Option<Self>> {
+        if !(input.peek(token::Paren) || input.peek(token::Brace)) {
+            return Ok(None);
+        }
+        let group: Group = input.parse()?;
+        if group.delimiter() != Delimiter::Parenthesis {
+            return Ok(Some(Self::Pattern(group)));
+        }
+        if let Ok(nested) = Self::parse_nested.parse2(group.stream()) {
+            return Ok(Some(nested));
+        }
+        (|input: ParseStream| {
+            Punctuated::<Pat, Token![,]>::parse_terminated_with(input, Pat::parse_multi)
+        })
+        .parse2(group.stream())
+        .map_err(|err| syn::Error::new(err.span(), format!("invalid payload pattern: {err}")))?;
+        Ok(Some(Self::Pattern(group)))
+    }
+
+    fn parse_nested(input: ParseStream) -> syn::Result<Self> {
+        let path: Path = input.parse()?;
+        if path.segments.len() != 2 {
+            Err(syn::Error::new_spanned(&path, "Expected Enum::Variant"))?
+        }
+        Ok(Self::Nested {
+            enum_ident: ContainerIdent(path.segments[0].ident.clone()),
+            variant_ident: VariantIdent(path.segments[1].ident.clone()),
+            payload: Self::parse_optional(input)?.map(Box::new),
+        })
+    }
+}
+
+impl Parse for VariantAnnotation {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let span = input.span();
+        if input.peek(Ident) && input.peek2(Token![=]) {
+            let integer_type: Ident = input.parse()?;
+            if !is_integer_type(&integer_type) {
+                Err(syn::Error::new(
+                    integer_type.span(),
+                    format!("expected an integer type, found `{integer_type}`"),
+                ))?
+            }
+            input.parse::<Token![=]>()?;
+            return Ok(Self::Integer {
+                span,
+                integer_type,
+                pattern: Pat::parse_multi(input)?,
+            });
+        }
+        let path: Path = input.parse()?;
+        let payload = Payload::parse_optional(input)?;
+        if payload.is_some() && path.segments.len() != 2 {
+            Err(syn::Error::new_spanned(
+                &path,
+                "Expected Enum::Variant before a payload pattern",
+            ))?
+        }
+        let guard = if input.peek(Token![if]) {
+            input.parse::<Token![if]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        if path.is_ident("skip") && payload.is_none() && guard.is_none() {
+            Ok(Self::Skip { span })
+        } else if path.segments.len() == 1 {
+            Ok(Self::EnumOnly {
+                span,
+                enum_ident: ContainerIdent(path.segments[0].ident.clone()),
+                guard,
+            })
+        } else if path.segments.len() == 2 {
+            Ok(Self::EnumVariant {
+                span,
+                enum_ident: ContainerIdent(path.segments[0].ident.clone()),
+                variant_ident: VariantIdent(path.segments[1].ident.clone()),
+                payload,
+                guard,
+            })
+        } else {
+            Err(syn::Error::new_spanned(
+                path,
+                "Expected Enum or Enum::Variant",
+            ))
+        }
+    }
+}
+
+/// The annotations of a field.
+pub struct FieldAnnotations {
+    /// The source fields the field is converted from.
+    pub fields_annotations: Vec<FieldAnnotation>,
+    /// The options of the annotations.
+    pub options: FieldOptions,
+    /// The span of the field.
+    pub field_span: Span,
+}
+
+/// Options applying to a field whatever the source enum it is converted from.
+#[derive(Clone, Default)]
+pub struct FieldOptions {
+    /// Async function used by EnumFromAsync to convert the field.
+    pub with: Option<Path>,
+    /// Whether the field is moved without conversion.
+    pub as_is: bool,
+    /// Whether the content of the field, a standard container, is converted element by element.
+    pub map: bool,
+}
+
+enum FieldItem {
+    Annotation(FieldAnnotation),
+    With(Path),
+    AsIs(Ident),
+    Map(Ident),
+}
+
+impl Parse for FieldItem {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        // Annotations always start with `SourceEnum::`.
+        if !input.peek(Ident) || input.peek2(Token![::]) {
+            return input.parse().map(Self::Annotation);
+        }
+        let ident: Ident = input.parse()?;
+        if ident == "as_is" && !input.peek(Token![=]) {
+            return Ok(Self::AsIs(ident));
+        }
+        if ident == "map" && !input.peek(Token![=]) {
+            return Ok(Self::Map(ident));
+        }
+        if ident == "with" && input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            return Ok(Self::With(input.parse()?));
+        }
+        Err(syn::Error::new(
+            ident.span(),
+            format!("unknown option `{ident}`, expected `with`, `as_is` or `map`"),
+        ))
+    }
+}
+
+/// A source field the field is converted from, for example `Source::Variant.x`.
+#[derive(Clone)]
+pub struct FieldAnnotation {
+    /// The source enum.
+    pub source_enum: ContainerIdent,
+    /// The source variant.
+    pub source_variant: VariantIdent,
+    /// The source field.
+    pub source_field: FieldRef,
+    /// The span of the source enum in the annotation.
+    pub enum_span: Span,
+    /// The span of the source variant in the annotation.
+    pub variant_span: Span,
+    /// The span of the source field in the annotation.
+    pub field_span: Span,
+}
+
+impl Parse for FieldAnnotation {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut path: Path = input.parse()?;
+        if path.segments.len() == 2 {
+            input.parse::<Token![.]>()?;
+            let field_span = input.span();
+            let source_field = if let Ok(ident) = input.parse::<Ident>() {
+                FieldRef::FieldIdent(FieldIdent(ident))
+            } else if let Ok(lit) = input.parse::<LitInt>() {
+                FieldRef::FieldPos(lit.base10_parse()?)
+            } else {
+                Err(syn::Error::new(
+                    field_span,
+                    "Expected either a field identifier or a field position",
+                ))?
+            };
+            let variant_segment = path.segments.pop().unwrap().into_value();
+            let enum_segment = path.segments.pop().unwrap().into_value();
+            Ok(FieldAnnotation {
+                enum_span: enum_segment.span(),
+                variant_span: variant_segment.span(),
+                field_span,
+                source_enum: ContainerIdent(enum_segment.ident),
+                source_variant: VariantIdent(variant_segment.ident),
+                source_field,
+            })
+        } else {
+            Err(syn::Error::new_spanned(
+                path,
+                "Expected SourceEnum::SourceVariant.field_name",
+            ))
+        }
+    }
+}
+
+fn extract_container_annotations(
+    container_attrs: &[Attribute],
+) -> syn::Result<(Vec<ContainerAnnotation>, Vec<Ident>)> {
+    let mut integer_sources = Vec::new();
+    let res = container_attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("enum_from"))
+        .map(|attr| {
+            let build_err = || {
+                syn::Error::new(
+                    attr.span(),
+                    "expected a list of source enums, for example #[enum_from(Source1, Source2)]",
+                )
+            };
+
+            match &attr.meta {
+                Meta::List(meta_list) => meta_list
+                    .parse_args_with(|input: ParseStream| {
+                        Punctuated::<ContainerItem, Token![,]>::parse_terminated(input)
+                    })
+                    .and_then(|items| {
+                        if items.empty_or_trailing() {
+                            return Err(build_err());
+                        }
+                        let mut source_enums = Vec::new();
+                        let mut has_integer_sources = false;
+                        let mut options = ContainerOptions::default();
+                        for item in items {
+                            match item {
+                                ContainerItem::SourceEnum(ident, declared_variants) => {
+                                    source_enums.push((ContainerIdent(ident), declared_variants))
+                                }
+                                ContainerItem::Integer(ident) => {
+                                    if integer_sources.contains(&ident) {
+                                        Err(syn::Error::new_spanned(
+                                            &ident,
+                                            format!("`{ident}` is already specified"),
+                                        ))?
+                                    }
+                                    integer_sources.push(ident);
+                                    has_integer_sources = true;
+                                }
+                                ContainerItem::Error(error) => {
+                                    if options.error.is_some() {
+                                        Err(syn::Error::new_spanned(
+                                            &error,
+                                            "the error type is already specified",
+                                        ))?
+                                    }
+                                    options.error = Some(error);
+                                }
+                                ContainerItem::AsIs(as_is) => {
+                                    if options.as_is {
+                                        Err(syn::Error::new_spanned(
+                                            &as_is,
+                                            "`as_is` is already specified",
+                                        ))?
+                                    }
+                                    options.as_is = true;
+                                }
+                                ContainerItem::Registered(registered) => {
+                                    if options.registered {
+                                        Err(syn::Error::new_spanned(
+                                            &registered,
+                                            "`registered` is already specified",
+                                        ))?
+                                    }
+                                    options.registered = true;
+                                }
+                                ContainerItem::All(all) => {
+                                    if options.all {
+                                        Err(syn::Error::new_spanned(
+                                            &all,
+                                            "`all` is already specified",
+                                        ))?
+                                    }
+                                    options.all = true;
+                                }
+                                ContainerItem::ByDiscriminant(by_discriminant) => {
+                                    if options.by_discriminant {
+                                        Err(syn::Error::new_spanned(
+                                            &by_discriminant,
+                                            "`by_discriminant` is already specified",
+                                        ))?
+                                    }
+                                    options.by_discriminant = true;
+                                }
+                                ContainerItem::Debug(debug) => {
+                                    if options.debug {
+                                        Err(syn::Error::new_spanned(
+                                            &debug,
+                                            "`debug` is already specified",
+                                        ))?
+                                    }
+                                    options.debug = true;
+                                }
+                                ContainerItem::Mapping(mapping) => {
+                                    if options.mapping {
+                                        Err(syn::Error::new_spanned(
+                                            &mapping,
+                                            "`mapping` is already specified",
+                                        ))?
+                                    }
+                                    options.mapping = true;
+                                }
+                                ContainerItem::Renaming(option, value) => {
+                                    let option = option.to_string();
+                                    if FIELD_RENAMING_OPTIONS.contains(&option.as_str()) {
+                                        options.field_renaming.set(&option, &value)?
+                                    } else {
+                                        options.renaming.set(&option, &value)?
+                                    }
+                                }
+                            }
+                        }
+                        if source_enums.is_empty() && !has_integer_sources {
+                            Err(build_err())
+                        } else {
+                            Ok(source_enums
+                                .into_iter()
+                                .map(|(source_enum, declared_variants)| ContainerAnnotation {
+                                    source_enum,
+                                    declared_variants,
+                                    options: options.clone(),
+                                })
+                                .collect::<Vec<_>>())
+                        }
+                    }),
+                Meta::Path(_) | Meta::NameValue(_) => Err(build_err()),
+            }
+        })
+        .collect_errors()?
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+    Ok((res, integer_sources))
+}
+
+fn extract_variants_annotations(
+    data_enum: DataEnum,
+) -> syn::Result<Vec<(Variant, VariantAnnotations)>> {
+    data_enum
+        .variants
+        .into_iter()
+        .map(|variant| {
+            extract_variant_annotations(&variant).map(|annotations| (variant, annotations))
+        })
+        .collect_errors()
+}
+
+fn extract_variant_annotations(variant: &Variant) -> syn::Result<VariantAnnotations> {
+    let variant_annotations = variant
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("enum_from"))
+        .map(|attr| {
+            let build_err = || {
+                syn::Error::new(
+                    attr.span(),
+                    "expected either #[enum_from] (if there is no ambiguity) or a list of variants, for example #[enum_from(Source1::VariantA, Source2::VariantB)]",
+                )
+            };
+            match &attr.meta {
+                Meta::Path(_) => Ok(vec![VariantAnnotation::Nothing { span: attr.span() }]),
+                Meta::List(meta_list) => {
+                    meta_list.parse_args_with(|input: ParseStream| {
+                        Punctuated::<VariantAnnotation, Token![,]>::parse_terminated(input)
+                            .and_then(|annotations| {
+                                if annotations.empty_or_trailing() {
+                                    Err(build_err())
+                                } else {
+                                    Ok(annotations.into_iter().collect())
+                                }
+                            })
+                    })
+                },
+                Meta::NameValue(_) => Err(build_err()),
+            }
+        })
+        .collect_errors()
+        .map(|annotations| annotations.into_iter().flatten().collect::<Vec<_>>());
+
+    let fields_annotations = variant
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(pos, field)| {
+            let annotations = extract_field_annotations(field);
+            match &field.ident {
+                Some(field_ident) => annotations.map(|field_annotations| {
+                    (
+                        FieldRef::FieldIdent(FieldIdent(field_ident.clone())),
+                        field_annotations,
+                    )
+                }),
+                None => annotations
+                    .map(|field_annotations| (FieldRef::FieldPos(pos), field_annotations)),
+            }
+        })
+        .collect_errors()
+        .map(|annotations| annotations.into_iter().collect());
+
+    let (variant_annotations, fields_annotations) =
+        errors::join(variant_annotations, fields_annotations)?;
+    Ok(VariantAnnotations {
+        variant_annotations,
+        fields_annotations,
+    })
+}
+
+fn extract_field_annotations(field: &Field) -> syn::Result<FieldAnnotations> {
+    let mut options = FieldOptions::default();
+    let fields_annotations = field.attrs.iter()
+        .filter(|attr| attr.path().is_ident("enum_from"))
+        .map(|attr| {
+            let build_err = || {
+                syn::Error::new(
+                    attr.span(),
+                    "expected a list of field names, for example #[enum_from(Source1::VariantA.field_x, Source2::VariantB.field_y)]",
+                )
+            };
+
+            match &attr.meta {
+                Meta::Path(_) | Meta::NameValue(_) => Err(build_err()),
+                Meta::List(meta_list) => {
+                    meta_list.parse_args_with(|input: ParseStream| {
+                        Punctuated::<FieldItem, Token![,]>::parse_terminated(input)
+                    })
+                    .and_then(|items| {
+                        if items.empty_or_trailing() {
+                            return Err(build_err());
+                        }
+                        let mut annotations = Vec::new();
+                        for item in items {
+                            match item {
+                                FieldItem::Annotation(annotation) => annotations.push(annotation),
+                                FieldItem::With(with) => {
+                                    if options.with.is_some() {
+                                        Err(syn::Error::new_spanned(
+                                            &with,
+                                            "the conversion function is already specified",
+                                        ))?
+                                    }
+                                    options.with = Some(with);
+                                }
+                                FieldItem::AsIs(as_is) => {
+                                    if options.as_is {
+                                        Err(syn::Error::new_spanned(
+                                            &as_is,
+                                            "`as_is` is already specified",
+                                        ))?
+                                    }
+                                    options.as_is = true;
+                                }
+                                FieldItem::Map(map) => {
+                                    if options.map {
+                                        Err(syn::Error::new_spanned(
+                                            &map,
+                                            "`map` is already specified",
+                                        ))?
+                                    }
+                                    options.map = true;
+                                }
+                            }
+                        }
+                        Ok(annotations)
+                    })
+                }
+            }
+        }).collect_errors()?
+        .into_iter()
+        .flatten()
+        .collect();
+
+    if let (Some(with), true) = (&options.with, options.as_is) {
+        Err(syn::Error::new_spanned(
+            with,
+            "a field moved `as_is` cannot have a conversion function",
+        ))?
+    }
+    if let (Some(with), true) = (&options.with, options.map) {
+        Err(syn::Error::new_spanned(
+            with,
+            "a field converted element by element with `map` cannot have a conversion function",
+        ))?
+    }
+    if options.as_is && options.map {
+        Err(syn::Error::new_spanned(
+            field,
+            "a field cannot be both moved `as_is` and converted element by element with `map`",
+        ))?
+    }
+
+    Ok(FieldAnnotations {
+        fields_annotations,
+        options,
+        field_span: field.span(),
+    })
+}
@@ -0,0 +1,6 @@
+//! The `#[enum_into]` annotations of `EnumInto`, converting the variants of the annotated enum
+//! into the variants of target enums.
+pub mod mapping;
+pub mod parser;
+
+pub use self::{mapping::EnumIntoMapping, parser::ParsedEnumInto};
@@ -0,0 +1,655 @@
+//! The validated mapping of the variants of the annotated enum into the target variants.
+use std::collections::{BTreeMap, HashMap};
+
+use indexmap::{IndexMap, map::Entry};
+use proc_macro2::{Span, TokenStream};
+use syn::{Expr, Fields, FieldsNamed, FieldsUnnamed, Ident, Variant, spanned::Spanned as _};
+
+use crate::{
+    discriminants::discriminants,
+    enum_into::parser::{
+        ContainerAnnotation, ContainerOptions, FieldAnnotation, FieldAnnotations, FieldOptions,
+        ParsedEnumInto, VariantAnnotation,
+    },
+    errors::{CollectErrors, Errors},
+    idents::{ContainerIdent, FieldIdent, FieldRef, VariantIdent},
+    renaming::Renaming,
+    suggestions::did_you_mean,
+};
+
+/// The conversions of the annotated enum, checked against its definition.
+/// Once constructed, the code generation should not fail.
+pub struct EnumIntoMapping {
+    /// The target enums, in the order of the container annotations.
+    pub target_enums: IndexMap<ContainerIdent, TargetEnum>,
+    /// The annotated enum.
+    pub source_enum: ContainerIdent,
+    /// The variants of the annotated enum, in declaration order.
+    pub source_variants: IndexMap<VariantIdent, SourceVariant>,
+    /// The integer types the annotated enum is converted into.
+    pub integer_targets: Vec<IntegerTarget>,
+}
+
+/// An integer type the variants of the source enum are converted into.
+pub struct IntegerTarget {
+    /// The integer type.
+    pub integer_type: Ident,
+    /// The integer each variant is converted into, in their declaration order.
+    pub values: Vec<(VariantIdent, IntegerValue)>,
+}
+
+/// The integer a variant is converted into.
+pub enum IntegerValue {
+    /// The value of the variant annotation.
+    Value(Expr),
+    /// The discriminant of the variant, as an expression.
+    Discriminant(TokenStream),
+}
+
+/// A target enum with the conversions into its variants.
+pub struct TargetEnum {
+    /// The options of the container annotation listing the target enum.
+    pub options: ContainerOptions,
+    /// The arms converting the source variants.
+    pub variants_mapping: VariantsMapping,
+}
+
+/// A variant of the annotated enum.
+pub struct SourceVariant {
+    /// The definition of the variant.
+    pub variant: Variant,
+    /// The options of the annotated fields.
+    pub fields_options: IndexMap<FieldRef, FieldOptions>,
+}
+
+/// For each source variant, the arm converting it into the target enum.
+pub struct VariantsMapping(pub IndexMap<VariantIdent, Vec<VariantArm>>);
+
+/// The conversion of a source variant into a target variant.
+pub struct VariantArm {
+    /// The variants of other enums wrapped in the target variant, from the outermost to the
+    /// innermost one. When not empty, the fields are mapped to the innermost variant.
+    pub inner_variants: InnerVariants,
+    /// How the fields are converted.
+    pub variant_mapping: VariantMapping,
+}
+
+/// The enums and variants wrapping each other, from the outermost to the innermost one.
+pub type InnerVariants = Vec<(ContainerIdent, VariantIdent)>;
+
+/// The source variant of an arm, with the target field each source field is converted into.
+pub enum VariantMapping {
+    /// A unit source variant converted into a unit target variant.
+    UnitToUnit {
+        /// The source variant.
+        source_variant: VariantIdent,
+    },
+    /// A tuple source variant converted into a tuple target variant.
+    TupleToTuple {
+        /// The source variant.
+        source_variant: VariantIdent,
+        /// The target position of each source position.
+        fields_mapping: IndexMap<usize, usize>,
+    },
+    /// A tuple source variant converted into a struct target variant.
+    TupleToStruct {
+        /// The source variant.
+        source_variant: VariantIdent,
+        /// The target field of each source position.
+        fields_mapping: IndexMap<usize, FieldIdent>,
+    },
+    /// A struct source variant converted into a struct target variant.
+    StructToStruct {
+        /// The source variant.
+        source_variant: VariantIdent,
+        /// The target field of each source field, the missing ones having the same name.
+        fields_mapping: IndexMap<FieldIdent, FieldIdent>,
+    },
+    /// A struct source variant converted into a tuple target variant.
+    StructToTuple {
+        /// The source variant.
+        source_variant: VariantIdent,
+        /// The target position of each source field.
+        fields_mapping: IndexMap<FieldIdent, usize>,
+    },
+}
+
+impl VariantMapping {
+    /// The source variant of the arm.
+    pub fn source_variant(&self) -> &VariantIdent {
+        match self {
+            VariantMapping::UnitToUnit { source_variant } => source_variant,
+            VariantMapping::TupleToTuple { source_variant, .. } => source_variant,
+            VariantMapping::TupleToStruct { source_variant, .. } => source_variant,
+            VariantMapping::StructToStruct { source_variant, .. } => source_variant,
+            VariantMapping::StructToTuple { source_variant, .. } => source_variant,
+        }
+    }
+}
+
+impl TryFrom<ParsedEnumInto> for EnumIntoMapping {
+    type Error = syn::Error;
+
+    fn try_from(
+        ParsedEnumInto {
+            source_enum,
+            container_annotations,
+            integer_targets,
+            variants_annotations,
+        }: ParsedEnumInto,
+    ) -> Result<Self, Self::Error> {
+        if container_annotations.is_empty() && integer_targets.is_empty() {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "enum_into attribute with target enum names is required",
+            ));
+        }
+
+        let mut source_variants: IndexMap<VariantIdent, SourceVariant> = IndexMap::new();
+
+        let mut target_enums = container_annotations
+            .into_iter()
+            .map(
+                |ContainerAnnotation {
+                     target_enum,
+                     options,
+                 }| {
+                    (
+                        target_enum,
+                        TargetEnum {
+                            options,
+                            variants_mapping: VariantsMapping(IndexMap::new()),
+                        },
+                    )
+                },
+            )
+            .collect::<IndexMap<_, _>>();
+
+        let mut integer_targets = integer_targets
+            .into_iter()
+            .map(|integer_type| IntegerTarget {
+                integer_type,
+                values: Vec::new(),
+            })
+            .collect::<Vec<_>>();
+        let discriminants = discriminants(variants_annotations.iter().map(|(variant, _)| variant));
+
+        let mut errors = Errors::default();
+        for ((source_variant, mut variant_annotations), discriminant) in
+            variants_annotations.into_iter().zip(discriminants)
+        {
+            let (integer_annotations, variant_annotations_list): (Vec<_>, Vec<_>) =
+                variant_annotations
+                    .variant_annotations
+                    .into_iter()
+                    .partition(|annotation| {
+                        matches!(annotation, VariantAnnotation::Integer { .. })
+                    });
+            errors.ok(add_integer_values(
+                &mut integer_targets,
+                &source_variant,
+                integer_annotations,
+                discriminant,
+            ));
+            let mut target_variants = variant_annotations_list
+                .into_iter()
+                .filter_map(|variant_annotation| match variant_annotation {
+                    VariantAnnotation::Nothing | VariantAnnotation::Integer { .. } => None,
+                    VariantAnnotation::EnumOnly { span, enum_ident } => {
+                        Some((enum_ident, (None, Vec::new(), span)))
+                    }
+                    VariantAnnotation::EnumVariant {
+                        span,
+                        enum_ident,
+                        variant_ident,
+                        inner_variants,
+                    } => Some((enum_ident, (Some(variant_ident), inner_variants, span))),
+                })
+                .collect::<IndexMap<_, _>>();
+            let mut sorted_target_enums = target_enums.iter_mut().collect::<Vec<_>>();
+            sorted_target_enums.sort_by_key(|(target_enum, _)| target_enum.to_string());
+            let mut fields_targets = Vec::new();
+            for (target_enum, target_enum_data) in sorted_target_enums {
+                let (target_variant, inner_variants) = target_variants
+                    .shift_remove(target_enum)
+                    .map(|(target_variant, inner_variants, _span)| (target_variant, inner_variants))
+                    .unwrap_or_default();
+                fields_targets.extend(errors.ok(add_variant_arm(
+                    target_enum,
+                    target_enum_data,
+                    &source_variant,
+                    &mut variant_annotations.fields_annotations,
+                    target_variant,
+                    inner_variants,
+                )));
+            }
+
+            let fields_options = variant_annotations
+                .fields_annotations
+                .iter()
+                .map(|(field, annotations)| (field.clone(), annotations.options.clone()))
+                .collect();
+
+            errors.ok(check_unused_variants_annotations(
+                &target_enums,
+                target_variants,
+            ));
+            errors.ok(check_unused_fields_annotations(
+                &target_enums,
+                &fields_targets,
+                variant_annotations.fields_annotations,
+            ));
+
+            source_variants.insert(
+                VariantIdent(source_variant.ident.clone()),
+                SourceVariant {
+                    variant: source_variant,
+                    fields_options,
+                },
+            );
+        }
+        errors.finish()?;
+
+        Ok(EnumIntoMapping {
+            target_enums,
+            source_enum,
+            source_variants,
+            integer_targets,
+        })
+    }
+}
+
+/// Adds the integers the source variant is converted into: the integers of its annotations if
+/// any, otherwise its discriminant.
+fn add_integer_values(
+    integer_targets: &mut [IntegerTarget],
+    source_variant: &Variant,
+    integer_annotations: Vec<VariantAnnotation>,
+    discriminant: TokenStream,
+) -> syn::Result<()> {
+    let mut errors = Errors::default();
+    let mut values: IndexMap<Ident, Expr> = IndexMap::new();
+    for annotation in integer_annotations {
+        let VariantAnnotation::Integer {
+            span,
+            integer_type,
+            value,
+        } = annotation
+        else {
+            continue;
+        };
+        if !integer_targets
+            .iter()
+            .any(|integer_target| integer_target.integer_type == integer_type)
+        {
+            errors.push(syn::Error::new(
+                span,
+                did_you_mean(
+                    format!(
+                        "integer type `{integer_type}` is not specified in this enum's #[enum_into] annotation"
+                    ),
+                    &integer_type,
+                    integer_targets
+                        .iter()
+                        .map(|integer_target| &integer_target.integer_type),
+                ),
+            ));
+        } else {
+            match values.entry(integer_type) {
+                Entry::Occupied(entry) => errors.push(syn::Error::new(
+                    span,
+                    format!(
+                        "the `{}` value of this variant is already specified",
+                        entry.key()
+                    ),
+                )),
+                Entry::Vacant(entry) => {
+                    entry.insert(value);
+                }
+            }
+        }
+    }
+    if !integer_targets.is_empty() && !matches!(source_variant.fields, Fields::Unit) {
+        errors.push(syn::Error::new(
+            source_variant.ident.span(),
+            "only a unit variant can be converted into an integer",
+        ));
+    }
+    errors.finish()?;
+
+    for IntegerTarget {
+        integer_type,
+        values: variants_values,
+    } in integer_targets
+    {
+        let value = match values.shift_remove(integer_type) {
+            Some(value) => IntegerValue::Value(value),
+            None => IntegerValue::Discriminant(discriminant.clone()),
+        };
+        variants_values.push((VariantIdent(source_variant.ident.clone()), value));
+    }
+    Ok(())
+}
+
+/// Adds the arm converting the source variant into the target enum.
+/// Returns the enum and variant the fields are converted into.
+fn add_variant_arm(
+    target_enum: &ContainerIdent,
+    TargetEnum {
+        options,
+        variants_mapping: VariantsMapping(variants_mapping),
+    }: &mut TargetEnum,
+    source_variant: &Variant,
+    fields_annotations: &mut HashMap<FieldRef, FieldAnnotations>,
+    target_variant: Option<VariantIdent>,
+    inner_variants: InnerVariants,
+) -> syn::Result<(ContainerIdent, VariantIdent)> {
+    // Without explicit target variant, it is named after the source variant.
+    let target_variant = match target_variant {
+        Some(target_variant) => target_variant,
+        None => VariantIdent(options.renaming.apply(&source_variant.ident)?),
+    };
+    let (constructor_enum, constructor_variant) = inner_variants.last().map_or(
+        (target_enum, &target_variant),
+        |(enum_ident, variant_ident)| (enum_ident, variant_ident),
+    );
+
+    let fields_annotations =
+        extract_fields_annotations(fields_annotations, constructor_enum, constructor_variant)?;
+    let fields_target = (constructor_enum.clone(), constructor_variant.clone());
+    let variant_mapping = compute_variant_mapping(
+        constructor_enum,
+        constructor_variant,
+        fields_annotations,
+        &options.field_renaming,
+        &source_variant.fields,
+        VariantIdent(source_variant.ident.clone()),
+    )?;
+
+    variants_mapping
+        .entry(target_variant)
+        .or_default()
+        .push(VariantArm {
+            inner_variants,
+            variant_mapping,
+        });
+    Ok(fields_target)
+}
+
+fn compute_variant_mapping(
+    target_enum: &ContainerIdent,
+    target_variant: &VariantIdent,
+    fields_annotations: BTreeMap<FieldRef, FieldAnnotation>,
+    field_renaming: &Renaming,
+    fields: &Fields,
+    source_variant: VariantIdent,
+) -> syn::Result<VariantMapping> {
+    // The fields annotations are keyed by the fields of the source variant, so a unit variant
+    // has none.
+    match (
+        fields,
+        fields_annotations
+            .first_key_value()
+            .map(|(_, field_annotation)| &field_annotation.target_field),
+    ) {
+        (Fields::Unit, _) => Ok(VariantMapping::UnitToUnit { source_variant }),
+        (Fields::Unnamed(fields), None)
+        | (Fields::Unnamed(fields), Some(FieldRef::FieldPos(_))) => {
+            compute_tuple_to_tuple_variant_mapping(fields_annotations, fields, source_variant)
+        }
+        (Fields::Named(fields), None) | (Fields::Named(fields), Some(FieldRef::FieldIdent(_))) => {
+            compute_struct_to_struct_variant_mapping(
+                fields_annotations,
+                field_renaming,
+                fields,
+                source_variant,
+            )
+        }
+        (Fields::Unnamed(fields), Some(FieldRef::FieldIdent(_))) => {
+            compute_tuple_to_struct_variant_mapping(
+                target_enum,
+                target_variant,
+                fields_annotations,
+                fields,
+                source_variant,
+            )
+        }
+        (Fields::Named(fields), Some(FieldRef::FieldPos(_))) => {
+            compute_struct_to_tuple_variant_mapping(
+                target_enum,
+                target_variant,
+                fields_annotations,
+                fields,
+                source_variant,
+            )
+        }
+    }
+}
+
+fn compute_tuple_to_tuple_variant_mapping(
+    mut fields_annotations: BTreeMap<FieldRef, FieldAnnotation>,
+    fields: &FieldsUnnamed,
+    source_variant: VariantIdent,
+) -> syn::Result<VariantMapping> {
+    let mut fields_mapping = IndexMap::new();
+    for source_pos in 0..fields.unnamed.len() {
+        match fields_annotations.remove(&FieldRef::FieldPos(source_pos)) {
+            None => {}
+            Some(FieldAnnotation {
+                target_field: FieldRef::FieldPos(target_pos),
+                ..
+            }) => {
+                fields_mapping.insert(source_pos, target_pos);
+            }
+            Some(FieldAnnotation { field_span, .. }) => Err(syn::Error::new(
+                field_span,
+                "Unexpected mapping to named field while another field mapped to a positional field.",
+            ))?,
+        }
+    }
+
+    Ok(VariantMapping::TupleToTuple {
+        source_variant,
+        fields_mapping,
+    })
+}
+
+fn compute_struct_to_struct_variant_mapping(
+    mut fields_annotations: BTreeMap<FieldRef, FieldAnnotation>,
+    field_renaming: &Renaming,
+    fields: &FieldsNamed,
+    source_variant: VariantIdent,
+) -> syn::Result<VariantMapping> {
+    let mut fields_mapping = IndexMap::new();
+    for field in fields.named.iter() {
+        let source_field = FieldIdent(field.ident.clone().expect("Named fields have idents"));
+        match fields_annotations.remove(&FieldRef::FieldIdent(source_field.clone())) {
+            None => {}
+            Some(FieldAnnotation {
+                target_field: FieldRef::FieldIdent(target_field),
+                ..
+            }) => {
+                fields_mapping.insert(source_field, target_field);
+            }
+            Some(FieldAnnotation { field_span, .. }) => Err(syn::Error::new(
+                field_span,
+                "Unexpected mapping to positional field while another field mapped to a named field.",
+            ))?,
+        }
+    }
+    for field in fields.named.iter() {
+        let source_field = FieldIdent(field.ident.clone().expect("Named fields have idents"));
+        if let Entry::Vacant(entry) = fields_mapping.entry(source_field) {
+            let target_field = FieldIdent(field_renaming.apply(&entry.key().0)?);
+            entry.insert(target_field);
+        }
+    }
+
+    Ok(VariantMapping::StructToStruct {
+        source_variant,
+        fields_mapping,
+    })
+}
+
+fn compute_struct_to_tuple_variant_mapping(
+    target_enum: &ContainerIdent,
+    target_variant: &VariantIdent,
+    mut fields_annotations: BTreeMap<FieldRef, FieldAnnotation>,
+    fields: &FieldsNamed,
+    source_variant: VariantIdent,
+) -> syn::Result<VariantMapping> {
+    let mut fields_mapping = IndexMap::new();
+    for field in fields.named.iter() {
+        let source_ident = FieldIdent(field.ident.clone().expect("Named fields have idents"));
+        match fields_annotations.remove(&FieldRef::FieldIdent(source_ident.clone())) {
+            None => Err(syn::Error::new(
+                field.span(),
+                format!(
+                    "Missing required mapping to named field for {target_enum}::{target_variant}"
+                ),
+            ))?,
+            Some(FieldAnnotation {
+                target_field: FieldRef::FieldPos(target_pos),
+                ..
+            }) => {
+                fields_mapping.insert(source_ident, target_pos);
+            }
+            Some(FieldAnnotation { field_span, .. }) => Err(syn::Error::new(
+                field_span,
+                "Unexpected mapping to named field while another field mapped to a positional field.",
+            ))?,
+        }
+    }
+
+    Ok(VariantMapping::StructToTuple {
+        source_variant,
+        fields_mapping,
+    })
+}
+
+fn compute_tuple_to_struct_variant_mapping(
+    target_enum: &ContainerIdent,
+    target_variant: &VariantIdent,
+    mut fields_annotations: BTreeMap<FieldRef, FieldAnnotation>,
+    fields: &FieldsUnnamed,
+    source_variant: VariantIdent,
+) -> syn::Result<VariantMapping> {
+    let mut fields_mapping = IndexMap::new();
+    for (source_pos, field) in fields.unnamed.iter().enumerate() {
+        match fields_annotations.remove(&FieldRef::FieldPos(source_pos)) {
+            None => Err(syn::Error::new(
+                field.span(),
+                format!(
+                    "Missing required mapping to named field for {target_enum}::{target_variant}"
+                ),
+            ))?,
+            Some(FieldAnnotation {
+                target_field: FieldRef::FieldIdent(target_ident),
+                ..
+            }) => {
+                fields_mapping.insert(source_pos, target_ident);
+            }
+            Some(FieldAnnotation { field_span, .. }) => Err(syn::Error::new(
+                field_span,
+                "Unexpected mapping to positional field while another field mapped to a named field.",
+            ))?,
+        }
+    }
+
+    Ok(VariantMapping::TupleToStruct {
+        source_variant,
+        fields_mapping,
+    })
+}
+
+fn check_unused_variants_annotations(
+    target_enums: &IndexMap<ContainerIdent, TargetEnum>,
+    target_variants: IndexMap<ContainerIdent, (Option<VariantIdent>, InnerVariants, Span)>,
+) -> syn::Result<()> {
+    let mut target_variants = target_variants.into_iter().collect::<Vec<_>>();
+    target_variants.sort_by_key(|(target_enum, _)| target_enum.to_string());
+    let mut errors = Errors::default();
+    for (target_enum, (_, _, span)) in target_variants {
+        errors.push(syn::Error::new(
+            span,
+            did_you_mean(
+                format!(
+                    "target enum `{target_enum}` is not specified in this enum's #[enum_into] annotation"
+                ),
+                &target_enum,
+                target_enums.keys(),
+            ),
+        ))
+    }
+    errors.finish()
+}
+
+/// Fails on the fields annotations which do not match any of the `fields_targets` the variant
+/// is converted into.
+fn check_unused_fields_annotations(
+    target_enums: &IndexMap<ContainerIdent, TargetEnum>,
+    fields_targets: &[(ContainerIdent, VariantIdent)],
+    fields_annotations: HashMap<FieldRef, FieldAnnotations>,
+) -> syn::Result<()> {
+    let mut errors = Errors::default();
+    let fields_annotations = fields_annotations.into_iter().collect::<BTreeMap<_, _>>();
+    for (_, field_annotations) in fields_annotations {
+        for field_annotation in field_annotations.fields_annotations {
+            if target_enums.contains_key(&field_annotation.target_enum) {
+                let target_variants = fields_targets
+                    .iter()
+                    .filter(|(target_enum, _)| *target_enum == field_annotation.target_enum)
+                    .map(|(_, target_variant)| target_variant);
+                errors.push(syn::Error::new(
+                    field_annotation.variant_span,
+                    did_you_mean(
+                        "Field mapping for unexpected enum and variant combination",
+                        &field_annotation.target_variant,
+                        target_variants,
+                    ),
+                ))
+            } else {
+                errors.push(syn::Error::new(
+                    field_annotation.enum_span,
+                    did_you_mean(
+                        "Field mapping for unknown enum",
+                        &field_annotation.target_enum,
+                        target_enums.keys(),
+                    ),
+                ))
+            }
+        }
+    }
+    errors.finish()
+}
+
+fn extract_fields_annotations(
+    fields_annotations: &mut HashMap<FieldRef, FieldAnnotations>,
+    target_enum: &ContainerIdent,
+    target_variant: &VariantIdent,
+) -> syn::Result<BTreeMap<FieldRef, FieldAnnotation>> {
+    let fields_annotations = fields_annotations.iter_mut().collect::<BTreeMap<_, _>>();
+    Ok(fields_annotations
+        .into_iter()
+        .filter_map(|(source_field, field_annotations)| {
+            let mut annotations = field_annotations
+                .fields_annotations
+                .extract_if(.., |field_annotation| {
+                    field_annotation.target_enum == *target_enum
+                        && field_annotation.target_variant == *target_variant
+                })
+                .collect::<Vec<_>>();
+            let annotation = annotations.pop();
+            if annotations.pop().is_some() {
+                Some(Err(syn::Error::new(
+                    field_annotations.field_span,
+                    format!("Multiple mapping found for target enum `{target_enum}`"),
+                )))
+            } else {
+                annotation.map(|annotation| Ok((source_field.clone(), annotation)))
+            }
+        })
+        .collect_errors()?
+        .into_iter()
+        .collect())
+}
@@ -0,0 +1,600 @@
+//! The parsing of the `#[enum_into]` annotations of the enum and of its variants and fields.
+use std::collections::HashMap;
+
+use proc_macro2::{Span, TokenStream};
+use syn::{
+    Attribute, Data, DataEnum, DeriveInput, Expr, Field, Ident, LitInt, LitStr, Meta, Path, Token,
+    Variant, parenthesized,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    spanned::Spanned,
+    token,
+};
+
+use crate::{
+    discriminants::is_integer_type,
+    errors::{self, CollectErrors},
+    idents::{ContainerIdent, FieldIdent, FieldRef, VariantIdent},
+    renaming::{FIELD_RENAMING_OPTIONS, RENAMING_OPTIONS, Renaming},
+};
+
+/// A "dumb" parser of the EnumInto annotations
+/// There is no check of consistency between annotations here.
+pub struct ParsedEnumInto {
+    /// The annotated enum.
+    pub source_enum: ContainerIdent,
+    /// The target enums listed in the container annotations, in their order.
+    pub container_annotations: Vec<ContainerAnnotation>,
+    /// The integer types listed in the container annotations, for example `i32` in
+    /// `#[enum_into(i32)]`.
+    pub integer_targets: Vec<Ident>,
+    /// The variants with their annotations, in declaration order.
+    pub variants_annotations: Vec<(Variant, VariantAnnotations)>,
+}
+
+impl ParsedEnumInto {
+    /// Parses the annotations of the enum given to the derive macro.
+    pub fn parse(input: TokenStream) -> syn::Result<ParsedEnumInto> {
+        let derive_input: DeriveInput = syn::parse2(input)?;
+
+        let data_enum = match derive_input.data {
+            Data::Enum(data) => data,
+            Data::Struct(_) | Data::Union(_) => Err(syn::Error::new(
+                Span::call_site(),
+                "EnumInto can only be derived for enums",
+            ))?,
+        };
+
+        let source_enum = ContainerIdent(derive_input.ident);
+        let ((container_annotations, integer_targets), variants_annotations) = errors::join(
+            extract_container_annotations(&derive_input.attrs),
+            extract_variants_annotations(data_enum),
+        )?;
+
+        Ok(ParsedEnumInto {
+            source_enum,
+            container_annotations,
+            integer_targets,
+            variants_annotations,
+        })
+    }
+}
+
+/// A target enum listed in a container annotation, for example `Target` in
+/// `#[enum_into(Target, as_is)]`.
+pub struct ContainerAnnotation {
+    /// The target enum.
+    pub target_enum: ContainerIdent,
+    /// The options of the annotation.
+    pub options: ContainerOptions,
+}
+
+/// Options applying to all the target enums listed in the same annotation.
+#[derive(Clone, Default)]
+pub struct ContainerOptions {
+    /// Whether the fields are moved without conversion by default.
+    pub as_is: bool,
+    /// Whether the annotations are checked against the registered definition of the enums.
+    pub registered: bool,
+    /// Whether the generated code is printed while compiling.
+    pub debug: bool,
+    /// Whether the conversions are described by an `EnumMapping` implementation.
+    pub mapping: bool,
+    /// How the target variants are named after the source variants when not specified.
+    pub(crate) renaming: Renaming,
+    /// How the target fields are named after the source fields when not specified.
+    pub(crate) field_renaming: Renaming,
+}
+
+enum ContainerItem {
+    TargetEnum(Ident),
+    Integer(Ident),
+    AsIs(Ident),
+    Registered(Ident),
+    Debug(Ident),
+    Mapping(Ident),
+    Renaming(Ident, LitStr),
+}
+
+impl Parse for ContainerItem {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ident: Ident = input.parse()?;
+        if !input.peek(Token![=]) {
+            if ident == "as_is" {
+                return Ok(Self::AsIs(ident));
+            }
+            if ident == "registered" {
+                return Ok(Self::Registered(ident));
+            }
+            if ident == "debug" {
+                return Ok(Self::Debug(ident));
+            }
+            if ident == "mapping" {
+                return Ok(Self::Mapping(ident));
+            }
+            if is_integer_type(&ident) {
+                return Ok(Self::Integer(ident));
+            }
+            return Ok(Self::TargetEnum(ident));
+        }
+        input.parse::<Token![=]>()?;
+        if RENAMING_OPTIONS
+            .iter()
+            .chain(FIELD_RENAMING_OPTIONS.iter())
+            .any(|option| ident == option)
+        {
+            Ok(Self::Renaming(ident, input.parse()?))
+        } else {
+            let expected = ["as_is", "debug", "mapping", "registered"]
+                .iter()
+                .chain(RENAMING_OPTIONS.iter())
+                .chain(FIELD_RENAMING_OPTIONS.iter())
+                .map(|option| format!("`{option}`"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            Err(syn::Error::new(
+                ident.span(),
+                format!("unknown option `{ident}`, expected one of {expected}"),
+            ))
+        }
+    }
+}
+
+/// The annotations of a variant and of its fields.
+pub struct VariantAnnotations {
+    /// The items of the variant annotations, in their order.
+    pub variant_annotations: Vec<VariantAnnotation>,
+    /// The annotations of each field.
+    pub fields_annotations: HashMap<FieldRef, FieldAnnotations>,
+}
+
+/// An item of a variant annotation.
+pub enum VariantAnnotation {
+    /// `#[enum_into]`, the variant is converted into the variants of the same name of all the
+    /// target enums.
+    Nothing,
+    /// `Target`, the variant is converted into the variant of the same name of the target enum.
+    EnumOnly {
+        /// The span of the item.
+        span: Span,
+        /// The target enum.
+        enum_ident: ContainerIdent,
+    },
+    /// `Target::Variant`, the variant is converted into this target variant.
+    EnumVariant {
+        /// The span of the item.
+        span: Span,
+        /// The target enum.
+        enum_ident: ContainerIdent,
+        /// The target variant.
+        variant_ident: VariantIdent,
+        /// The variants of other enums to wrap in the target variant, from the outermost to the
+        /// innermost one, for example `Kind::NotFound` in `Target::Io(Kind::NotFound)`.
+        inner_variants: Vec<(ContainerIdent, VariantIdent)>,
+    },
+    /// The integer the variant is converted into instead of its discriminant, for example
+    /// `#[enum_into(i32 = -1)]`.
+    Integer {
+        /// The span of the item.
+        span: Span,
+        /// The integer type.
+        integer_type: Ident,
+        /// The integer.
+        value: Expr,
+    },
+}
+
+impl Parse for VariantAnnotation {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let span = input.span();
+        if input.peek(Ident) && input.peek2(Token![=]) {
+            let integer_type: Ident = input.parse()?;
+            if !is_integer_type(&integer_type) {
+                Err(syn::Error::new(
+                    integer_type.span(),
+                    format!("expected an integer type, found `{integer_type}`"),
+                ))?
+            }
+            input.parse::<Token![=]>()?;
+            return Ok(Self::Integer {
+                span,
+                integer_type,
+                value: input.parse()?,
+            });
+        }
+        let path: Path = input.parse()?;
+        if path.segments.len() == 1 {
+            Ok(Self::EnumOnly {
+                span,
+                enum_ident: ContainerIdent(path.segments[0].ident.clone()),
+            })
+        } else if path.segments.len() == 2 {
+            let inner_variants = parse_inner_variants(input)?;
+            Ok(Self::EnumVariant {
+                span,
+                enum_ident: ContainerIdent(path.segments[0].ident.clone()),
+                variant_ident: VariantIdent(path.segments[1].ident.clone()),
+                inner_variants,
+            })
+        } else {
+            Err(syn::Error::new_spanned(
+                path,
+                "Expected Enum or Enum::Variant",
+            ))
+        }
+    }
+}
+
+/// Parses the optional `(Inner::Variant)` following a target variant, recursively.
+fn parse_inner_variants(input: ParseStream) -> syn::Result<Vec<(ContainerIdent, VariantIdent)>> {
+    if !input.peek(token::Paren) {
+        return Ok(Vec::new());
+    }
+    let content;
+    parenthesized!(content in input);
+    let path: Path = content.parse()?;
+    if path.segments.len() != 2 {
+        Err(syn::Error::new_spanned(
+            &path,
+            "Expected Enum::Variant inside the target variant",
+        ))?
+    }
+    let mut inner_variants = vec![(
+        ContainerIdent(path.segments[0].ident.clone()),
+        VariantIdent(path.segments[1].ident.clone()),
+    )];
+    inner_variants.extend(parse_inner_variants(&content)?);
+    if !content.is_empty() {
+        Err(content.error("Expected a single Enum::Variant inside the target variant"))?
+    }
+    Ok(inner_variants)
+}
+
+/// The annotations of a field.
+pub struct FieldAnnotations {
+    /// The target fields the field is converted into.
+    pub fields_annotations: Vec<FieldAnnotation>,
+    /// The options of the annotations.
+    pub options: FieldOptions,
+    /// The span of the field.
+    pub field_span: Span,
+}
+
+/// Options applying to a field whatever the target enum it is converted into.
+#[derive(Clone, Default)]
+pub struct FieldOptions {
+    /// Whether the field is moved without conversion.
+    pub as_is: bool,
+    /// Whether the content of the field, a standard container, is converted element by element.
+    pub map: bool,
+}
+
+enum FieldItem {
+    Annotation(FieldAnnotation),
+    AsIs(Ident),
+    Map(Ident),
+}
+
+impl Parse for FieldItem {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        // Annotations always start with `TargetEnum::`.
+        if !input.peek(Ident) || input.peek2(Token![::]) {
+            return input.parse().map(Self::Annotation);
+        }
+        let ident: Ident = input.parse()?;
+        if ident == "as_is" {
+            Ok(Self::AsIs(ident))
+        } else if ident == "map" {
+            Ok(Self::Map(ident))
+        } else {
+            Err(syn::Error::new(
+                ident.span(),
+                format!("unknown option `{ident}`, expected `as_is` or `map`"),
+            ))
+        }
+    }
+}
+
+/// A target field the field is converted into, for example `Target::Variant.x`.
+#[derive(Clone)]
+pub struct FieldAnnotation {
+    /// The target enum.
+    pub target_enum: ContainerIdent,
+    /// The target variant.
+    pub target_variant: VariantIdent,
+    /// The target field.
+    pub target_field: FieldRef,
+    /// The span of the target enum in the annotation.
+    pub enum_span: Span,
+    /// The span of the target variant in the annotation.
+    pub variant_span: Span,
+    /// The span of the target field in the annotation.
+    pub field_span: Span,
+}
+
+impl Parse for FieldAnnotation {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut path: Path = input.parse()?;
+        if path.segments.len() == 2 {
+            input.parse::<Token![.]>()?;
+            let field_span = input.span();
+            let target_field = if let Ok(ident) = input.parse::<Ident>() {
+                FieldRef::FieldIdent(FieldIdent(ident))
+            } else if let Ok(lit) = input.parse::<LitInt>() {
+                FieldRef::FieldPos(lit.base10_parse()?)
+            } else {
+                Err(syn::Error::new(
+                    field_span,
+                    "Expected either a field identifier or a field position",
+                ))?
+            };
+            let variant_segment = path.segments.pop().unwrap().into_value();
+            let enum_segment = path.segments.pop().unwrap().into_value();
+            Ok(FieldAnnotation {
+                enum_span: enum_segment.span(),
+                variant_span: variant_segment.span(),
+                field_span,
+                target_enum: ContainerIdent(enum_segment.ident),
+                target_variant: VariantIdent(variant_segment.ident),
+                target_field,
+            })
+        } else {
+            Err(syn::Error::new_spanned(
+                path,
+                "Expected TargetEnum::TargetVariant.field_name",
+            ))
+        }
+    }
+}
+
+fn extract_container_annotations(
+    container_attrs: &[Attribute],
+) -> syn::Result<(Vec<ContainerAnnotation>, Vec<Ident>)> {
+    let mut integer_targets = Vec::new();
+    let res = container_attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("enum_into"))
+        .map(|attr| {
+            let build_err = || {
+                syn::Error::new(
+                    attr.span(),
+                    "expected a list of target enums, for example #[enum_into(Target1, Target2)]",
+                )
+            };
+
+            match &attr.meta {
+                Meta::List(meta_list) => meta_list
+                    .parse_args_with(|input: ParseStream| {
+                        Punctuated::<ContainerItem, Token![,]>::parse_terminated(input)
+                    })
+                    .and_then(|items| {
+                        if items.empty_or_trailing() {
+                            return Err(build_err());
+                        }
+                        let mut target_enums = Vec::new();
+                        let mut has_integer_targets = false;
+                        let mut options = ContainerOptions::default();
+                        for item in items {
+                            match item {
+                                ContainerItem::TargetEnum(ident) => {
+                                    target_enums.push(ContainerIdent(ident))
+                                }
+                                ContainerItem::Integer(ident) => {
+                                    if integer_targets.contains(&ident) {
+                                        Err(syn::Error::new_spanned(
+                                            &ident,
+                                            format!("`{ident}` is already specified"),
+                                        ))?
+                                    }
+                                    integer_targets.push(ident);
+                                    has_integer_targets = true;
+                                }
+                                ContainerItem::AsIs(as_is) => {
+                                    if options.as_is {
+                                        Err(syn::Error::new_spanned(
+                                            &as_is,
+                                            "`as_is` is already specified",
+                                        ))?
+                                    }
+                                    options.as_is = true;
+                                }
+                                ContainerItem::Registered(registered) => {
+                                    if options.registered {
+                                        Err(syn::Error::new_spanned(
+                                            &registered,
+                                            "`registered` is already specified",
+                                        ))?
+                                    }
+                                    options.registered = true;
+                                }
+                                ContainerItem::Debug(debug) => {
+                                    if options.debug {
+                                        Err(syn::Error::new_spanned(
+                                            &debug,
+                                            "`debug` is already specified",
+                                        ))?
+                                    }
+                                    options.debug = true;
+                                }
+                                ContainerItem::Mapping(mapping) => {
+                                    if options.mapping {
+                                        Err(syn::Error::new_spanned(
+                                            &mapping,
+                                            "`mapping` is already specified",
+                                        ))?
+                                    }
+                                    options.mapping = true;
+                                }
+                                ContainerItem::Renaming(option, value) => {
+                                    let option = option.to_string();
+                                    if FIELD_RENAMING_OPTIONS.contains(&option.as_str()) {
+                                        options.field_renaming.set(&option, &value)?
+                                    } else {
+                                        options.renaming.set(&option, &value)?
+                                    }
+                                }
+                            }
+                        }
+                        if target_enums.is_empty() && !has_integer_targets {
+                            Err(build_err())
+                        } else {
+                            Ok(target_enums
+                                .into_iter()
+                                .map(|target_enum| ContainerAnnotation {
+                                    target_enum,
+                                    options: options.clone(),
+                                })
+                                .collect::<Vec<_>>())
+                        }
+                    }),
+                Meta::Path(_) | Meta::NameValue(_) => Err(build_err()),
+            }
+        })
+        .collect_errors()?
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+    Ok((res, integer_targets))
+}
+
+fn extract_variants_annotations(
+    data_enum: DataEnum,
+) -> syn::Result<Vec<(Variant, VariantAnnotations)>> {
+    data_enum
+        .variants
+        .into_iter()
+        .map(|variant| {
+            extract_variant_annotations(&variant).map(|annotations| (variant, annotations))
+        })
+        .collect_errors()
+}
+
+fn extract_variant_annotations(variant: &Variant) -> syn::Result<VariantAnnotations> {
+    let variant_annotations = variant
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("enum_into"))
+        .map(|attr| {
+            let build_err = || {
+                syn::Error::new(
+                    attr.span(),
+                    "expected a list of variants, for example #[enum_into(Target1::VariantA, Target2::VariantB)].\n\
+                    If there is only one target enum and the variant names are identical between source and target, #[enum_into] can be omitted.",
+                )
+            };
+            match &attr.meta {
+                Meta::Path(_) => Ok(vec![VariantAnnotation::Nothing]),
+                Meta::List(meta_list) => {
+                    meta_list.parse_args_with(|input: ParseStream| {
+                        Punctuated::<VariantAnnotation, Token![,]>::parse_terminated(input)
+                            .and_then(|annotations| {
+                                if annotations.empty_or_trailing() {
+                                    Err(build_err())
+                                } else {
+                                    Ok(annotations.into_iter().collect())
+                                }
+                            })
+                    })
+                },
+                Meta::NameValue(_) => Err(build_err()),
+            }
+        })
+        .collect_errors()
+        .map(|annotations| annotations.into_iter().flatten().collect::<Vec<_>>());
+
+    let fields_annotations = variant
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(pos, field)| {
+            let annotations = extract_field_annotations(field);
+            match &field.ident {
+                Some(field_ident) => annotations.map(|field_annotations| {
+                    (
+                        FieldRef::FieldIdent(FieldIdent(field_ident.clone())),
+                        field_annotations,
+                    )
+                }),
+                None => annotations
+                    .map(|field_annotations| (FieldRef::FieldPos(pos), field_annotations)),
+            }
+        })
+        .collect_errors()
+        .map(|annotations| annotations.into_iter().collect());
+
+    let (variant_annotations, fields_annotations) =
+        errors::join(variant_annotations, fields_annotations)?;
+    Ok(VariantAnnotations {
+        variant_annotations,
+        fields_annotations,
+    })
+}
+
+fn extract_field_annotations(field: &Field) -> syn::Result<FieldAnnotations> {
+    let mut options = FieldOptions::default();
+    let fields_annotations = field.attrs.iter()
+        .filter(|attr| attr.path().is_ident("enum_into"))
+        .map(|attr| {
+            let build_err = || {
+                syn::Error::new(
+                    attr.span(),
+                    "expected a list of field names, for example #[enum_into(Target1::VariantA.field_x, Target2::VariantB.field_y)]",
+                )
+            };
+
+            match &attr.meta {
+                Meta::Path(_) | Meta::NameValue(_) => Err(build_err()),
+                Meta::List(meta_list) => {
+                    meta_list.parse_args_with(|input: ParseStream| {
+                        Punctuated::<FieldItem, Token![,]>::parse_terminated(input)
+                    })
+                    .and_then(|items| {
+                        if items.empty_or_trailing() {
+                            return Err(build_err());
+                        }
+                        let mut annotations = Vec::new();
+                        for item in items {
+                            match item {
+                                FieldItem::Annotation(annotation) => annotations.push(annotation),
+                                FieldItem::AsIs(as_is) => {
+                                    if options.as_is {
+                                        Err(syn::Error::new_spanned(
+                                            &as_is,
+                                            "`as_is` is already specified",
+                                        ))?
+                                    }
+                                    options.as_is = true;
+                                }
+                                FieldItem::Map(map) => {
+                                    if options.map {
+                                        Err(syn::Error::new_spanned(
+                                            &map,
+                                            "`map` is already specified",
+                                        ))?
+                                    }
+                                    options.map = true;
+                                }
+                            }
+                        }
+                        Ok(annotations)
+                    })
+                }
+            }
+        }).collect_errors()?
+        .into_iter()
+        .flatten()
+        .collect();
+
+    if options.as_is && options.map {
+        Err(syn::Error::new_spanned(
+            field,
+            "a field cannot be both moved `as_is` and converted element by element with `map`",
+        ))?
+    }
+
+    Ok(FieldAnnotations {
+        fields_annotations,
+        options,
+        field_span: field.span(),
+    })
+}
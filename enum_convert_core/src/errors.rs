@@ -0,0 +1,65 @@
+//! The accumulation of the errors, to report all of them at once.
+use indexmap::IndexMap;
+
+/// Accumulates the errors, to report all of them at once instead of stopping at the first one.
+#[derive(Default)]
+pub struct Errors(Option<syn::Error>);
+
+impl Errors {
+    /// Records the error.
+    pub fn push(&mut self, error: syn::Error) {
+        match &mut self.0 {
+            Some(errors) => errors.combine(error),
+            None => self.0 = Some(error),
+        }
+    }
+
+    /// Returns the value of the result, or records its error.
+    pub fn ok<T>(&mut self, result: syn::Result<T>) -> Option<T> {
+        result.map_err(|error| self.push(error)).ok()
+    }
+
+    /// Fails with all the recorded errors, if any.
+    pub fn finish(self) -> syn::Result<()> {
+        self.0.map_or(Ok(()), Err)
+    }
+}
+
+/// Collects the values of the results, or fails with all their errors.
+pub(crate) fn collect<T>(results: impl IntoIterator<Item = syn::Result<T>>) -> syn::Result<Vec<T>> {
+    let mut errors = Errors::default();
+    let values = results
+        .into_iter()
+        .filter_map(|result| errors.ok(result))
+        .collect();
+    errors.finish().map(|()| values)
+}
+
+/// Joins the values of two results, or fails with the errors of both.
+pub(crate) fn join<A, B>(a: syn::Result<A>, b: syn::Result<B>) -> syn::Result<(A, B)> {
+    match (a, b) {
+        (Ok(a), Ok(b)) => Ok((a, b)),
+        (Err(error), Ok(_)) | (Ok(_), Err(error)) => Err(error),
+        (Err(mut error), Err(other)) => {
+            error.combine(other);
+            Err(error)
+        }
+    }
+}
+
+/// Extension of the iterators of results to collect all their errors.
+pub(crate) trait CollectErrors<T>: Iterator<Item = syn::Result<T>> + Sized {
+    /// Collects the values of the results, or fails with all their errors.
+    fn collect_errors(self) -> syn::Result<Vec<T>> {
+        collect(self)
+    }
+}
+
+impl<T, I: Iterator<Item = syn::Result<T>>> CollectErrors<T> for I {}
+
+/// Returns the entries of the map sorted by name, to report the errors in a stable order.
+pub fn sorted_by_name<K: ToString, V>(map: &IndexMap<K, V>) -> Vec<(&K, &V)> {
+    let mut entries = map.iter().collect::<Vec<_>>();
+    entries.sort_by_key(|(key, _)| key.to_string());
+    entries
+}
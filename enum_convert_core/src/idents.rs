@@ -1,8 +1,10 @@
+//! The identifiers of the enums, variants and fields, kept apart by their types.
 use std::fmt::Display;
 
 use quote::ToTokens;
 use syn::Ident;
 
+/// The identifier of an enum.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ContainerIdent(pub Ident);
 
@@ -18,6 +20,7 @@ impl ToTokens for ContainerIdent {
     }
 }
 
+/// The identifier of a variant.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct VariantIdent(pub Ident);
 
@@ -33,6 +36,7 @@ impl ToTokens for VariantIdent {
     }
 }
 
+/// The identifier of a named field.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct FieldIdent(pub Ident);
 
@@ -49,7 +53,10 @@ impl ToTokens for FieldIdent {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+/// A field of a variant, by position or by name.
 pub enum FieldRef {
+    /// The field at this position in a tuple variant.
     FieldPos(usize),
+    /// The field with this name in a struct variant.
     FieldIdent(FieldIdent),
 }
@@ -0,0 +1,83 @@
+//! Parsing and validation of the annotations of the [enum_convert](https://docs.rs/enum_convert)
+//! derive macros.
+//!
+//! This crate allows other derive macros to accept exactly the same `#[enum_from]` and
+//! `#[enum_into]` syntax. The annotated enum is parsed into a [`ParsedEnumFrom`] or a
+//! [`ParsedEnumInto`], then checked against its definition into an [`EnumFromMapping`] or an
+//! [`EnumIntoMapping`], which lists for each variant of the other enums how it is converted.
+//! The errors are reported as [`syn::Error`]s, all of them at once.
+//!
+//! [`ParsedEnumFrom`]: enum_from::ParsedEnumFrom
+//! [`ParsedEnumInto`]: enum_into::ParsedEnumInto
+//! [`EnumFromMapping`]: enum_from::EnumFromMapping
+//! [`EnumIntoMapping`]: enum_into::EnumIntoMapping
+//!
+//! # Examples
+//!
+//! ```
+//! use enum_convert_core::enum_from::{EnumFromMapping, ParsedEnumFrom, mapping::VariantMapping};
+//! use quote::quote;
+//!
+//! let input = quote! {
+//!     #[enum_from(Source)]
+//!     enum Target {
+//!         #[enum_from]
+//!         Unit,
+//!         #[enum_from(Source::Point)]
+//!         Coordinates {
+//!             #[enum_from(Source::Point.x)]
+//!             abscissa: i64,
+//!             y: i64,
+//!         },
+//!     }
+//! };
+//! let mapping = ParsedEnumFrom::parse(input).and_then(EnumFromMapping::try_from)?;
+//!
+//! let source = &mapping.source_enums.values().next().unwrap();
+//! let arms = &source.variants_mapping.0;
+//! let point_arm = &arms.iter().find(|(variant, _)| variant.0 == "Point").unwrap().1[0];
+//! let VariantMapping::StructToStruct {
+//!     target_variant,
+//!     fields_mapping,
+//! } = &point_arm.variant_mapping
+//! else {
+//!     panic!("Source::Point is converted field by field");
+//! };
+//! assert_eq!(target_variant.0, "Coordinates");
+//! let fields = fields_mapping
+//!     .iter()
+//!     .map(|(target, source)| (target.to_string(), source.to_string()))
+//!     .collect::<Vec<_>>();
+//! assert_eq!(fields, [("abscissa".into(), "x".into()), ("y".into(), "y".into())]);
+//!
+//! let unknown = quote! {
+//!     #[enum_from(Source)]
+//!     enum Target {
+//!         #[enum_from(Other::Unit)]
+//!         Unit,
+//!     }
+//! };
+//! let error = ParsedEnumFrom::parse(unknown)
+//!     .and_then(EnumFromMapping::try_from)
+//!     .err()
+//!     .unwrap();
+//! assert_eq!(
+//!     error.to_string(),
+//!     "source enum `Other` is not specified in this enum's #[enum_from] annotation"
+//! );
+//! # Ok::<(), syn::Error>(())
+//! ```
+#![warn(missing_docs)]
+
+pub mod enum_from;
+pub mod enum_into;
+pub mod idents;
+mod renaming;
+
+// The helpers shared with the derive macros, which are not part of the API.
+#[doc(hidden)]
+pub mod discriminants;
+#[doc(hidden)]
+pub mod errors;
+#[doc(hidden)]
+pub mod suggestions;
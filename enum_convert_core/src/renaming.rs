@@ -0,0 +1,220 @@
+//! The renaming of the variants and fields of the annotated enum into those of the other enum.
+use syn::{Ident, LitStr};
+
+/// The options computing the name of a variant (or field) of the other enum from the name of a
+/// variant (or field) of the annotated enum, when it is not specified explicitly.
+/// The prefixes and suffixes are stripped first, then the case is converted and finally the
+/// prefixes and suffixes are added.
+#[derive(Clone, Default)]
+pub struct Renaming {
+    strip_prefix: Option<String>,
+    strip_suffix: Option<String>,
+    rename_all: Option<RenameRule>,
+    add_prefix: Option<String>,
+    add_suffix: Option<String>,
+}
+
+/// The options renaming the variants.
+pub const RENAMING_OPTIONS: [&str; 5] = [
+    "rename_all",
+    "strip_prefix",
+    "strip_suffix",
+    "add_prefix",
+    "add_suffix",
+];
+
+/// The options renaming the fields of the struct variants, in the same order as
+/// `RENAMING_OPTIONS`.
+pub const FIELD_RENAMING_OPTIONS: [&str; 5] = [
+    "rename_all_fields",
+    "strip_field_prefix",
+    "strip_field_suffix",
+    "add_field_prefix",
+    "add_field_suffix",
+];
+
+impl Renaming {
+    /// Sets the option `name`, which must be one of `RENAMING_OPTIONS` or
+    /// `FIELD_RENAMING_OPTIONS`.
+    pub fn set(&mut self, name: &str, value: &LitStr) -> syn::Result<()> {
+        let (slot, value) = match name {
+            "rename_all" | "rename_all_fields" => {
+                if self.rename_all.is_some() {
+                    Err(syn::Error::new(
+                        value.span(),
+                        format!("`{name}` is already specified"),
+                    ))?
+                }
+                self.rename_all = Some(RenameRule::parse(value)?);
+                return Ok(());
+            }
+            "strip_prefix" | "strip_field_prefix" => (&mut self.strip_prefix, value),
+            "strip_suffix" | "strip_field_suffix" => (&mut self.strip_suffix, value),
+            "add_prefix" | "add_field_prefix" => (&mut self.add_prefix, value),
+            "add_suffix" | "add_field_suffix" => (&mut self.add_suffix, value),
+            _ => unreachable!("the renaming options should have been checked"),
+        };
+        if slot.is_some() {
+            Err(syn::Error::new(
+                value.span(),
+                format!("`{name}` is already specified"),
+            ))?
+        }
+        *slot = Some(value.value());
+        Ok(())
+    }
+
+    /// Returns the name of the variant (or field) of the other enum.
+    pub fn apply(&self, ident: &Ident) -> syn::Result<Ident> {
+        if self.is_identity() {
+            return Ok(ident.clone());
+        }
+        let ident_str = ident.to_string();
+        let mut name = ident_str.as_str();
+        if let Some(stripped) = self
+            .strip_prefix
+            .as_deref()
+            .and_then(|prefix| name.strip_prefix(prefix))
+        {
+            name = stripped;
+        }
+        if let Some(stripped) = self
+            .strip_suffix
+            .as_deref()
+            .and_then(|suffix| name.strip_suffix(suffix))
+        {
+            name = stripped;
+        }
+        let name = match self.rename_all {
+            Some(rule) => rule.apply(name),
+            None => name.to_string(),
+        };
+        let name = format!(
+            "{}{name}{}",
+            self.add_prefix.as_deref().unwrap_or_default(),
+            self.add_suffix.as_deref().unwrap_or_default(),
+        );
+        syn::parse_str::<Ident>(&name)
+            .map(|renamed| Ident::new(&renamed.to_string(), ident.span()))
+            .map_err(|_| {
+                syn::Error::new(
+                    ident.span(),
+                    format!("`{ident}` is renamed into `{name}` which is not a valid identifier"),
+                )
+            })
+    }
+
+    fn is_identity(&self) -> bool {
+        self.strip_prefix.is_none()
+            && self.strip_suffix.is_none()
+            && self.rename_all.is_none()
+            && self.add_prefix.is_none()
+            && self.add_suffix.is_none()
+    }
+}
+
+#[derive(Clone, Copy)]
+enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+}
+
+impl RenameRule {
+    const ALL: [(&str, RenameRule); 6] = [
+        ("lowercase", RenameRule::Lower),
+        ("UPPERCASE", RenameRule::Upper),
+        ("PascalCase", RenameRule::Pascal),
+        ("camelCase", RenameRule::Camel),
+        ("snake_case", RenameRule::Snake),
+        ("SCREAMING_SNAKE_CASE", RenameRule::ScreamingSnake),
+    ];
+
+    fn parse(value: &LitStr) -> syn::Result<Self> {
+        let value_str = value.value();
+        Self::ALL
+            .iter()
+            .find(|(name, _)| *name == value_str)
+            .map(|(_, rule)| *rule)
+            .ok_or_else(|| {
+                let expected = Self::ALL
+                    .iter()
+                    .map(|(name, _)| format!("\"{name}\""))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                syn::Error::new(
+                    value.span(),
+                    format!("unknown case `{value_str}`, expected one of {expected}"),
+                )
+            })
+    }
+
+    fn apply(self, name: &str) -> String {
+        let words = split_words(name);
+        match self {
+            RenameRule::Lower => words.concat().to_lowercase(),
+            RenameRule::Upper => words.concat().to_uppercase(),
+            RenameRule::Pascal => words.iter().map(|word| capitalize(word)).collect(),
+            RenameRule::Camel => words
+                .iter()
+                .enumerate()
+                .map(|(i, word)| {
+                    if i == 0 {
+                        word.to_lowercase()
+                    } else {
+                        capitalize(word)
+                    }
+                })
+                .collect(),
+            RenameRule::Snake => words
+                .iter()
+                .map(|word| word.to_lowercase())
+                .collect::<Vec<_>>()
+                .join("_"),
+            RenameRule::ScreamingSnake => words
+                .iter()
+                .map(|word| word.to_uppercase())
+                .collect::<Vec<_>>()
+                .join("_"),
+        }
+    }
+}
+
+/// Splits an identifier in any case into its words, for example `HTTPError`, `http_error` and
+/// `httpError` are all split into `http`/`HTTP` and `error`/`Error`.
+fn split_words(name: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    for part in name.split('_').filter(|part| !part.is_empty()) {
+        let chars = part.char_indices().collect::<Vec<_>>();
+        let mut start = 0;
+        for window in 1..chars.len() {
+            let (i, current) = chars[window];
+            let previous = chars[window - 1].1;
+            let next = chars.get(window + 1).map(|(_, next)| *next);
+            let is_boundary = current.is_uppercase()
+                && (previous.is_lowercase()
+                    || previous.is_numeric()
+                    || (previous.is_uppercase() && next.is_some_and(char::is_lowercase)));
+            if is_boundary {
+                words.push(&part[start..i]);
+                start = i;
+            }
+        }
+        words.push(&part[start..]);
+    }
+    words
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first
+            .to_uppercase()
+            .chain(chars.flat_map(char::to_lowercase))
+            .collect(),
+        None => String::new(),
+    }
+}
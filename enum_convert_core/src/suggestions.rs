@@ -0,0 +1,50 @@
+//! The hints suggesting the intended name when a name is misspelled.
+/// Appends a "did you mean" hint to the message when one of the candidates is close enough to
+/// `name` to be a likely typo.
+pub fn did_you_mean<T: ToString>(
+    message: impl Into<String>,
+    name: &impl ToString,
+    candidates: impl IntoIterator<Item = T>,
+) -> String {
+    let message = message.into();
+    match closest(&name.to_string(), candidates) {
+        Some(candidate) => format!("{message}, did you mean `{candidate}`?"),
+        None => message,
+    }
+}
+
+/// Returns the candidate with the smallest edit distance to `name`, ignoring the candidates
+/// which differ by more than a third of the length of `name`.
+/// A candidate differing only by its case is always considered close.
+fn closest<T: ToString>(name: &str, candidates: impl IntoIterator<Item = T>) -> Option<String> {
+    let max_distance = (name.chars().count() / 3).max(1);
+    candidates
+        .into_iter()
+        .map(|candidate| candidate.to_string())
+        .filter(|candidate| candidate != name)
+        .filter_map(|candidate| {
+            let distance = if candidate.eq_ignore_ascii_case(name) {
+                0
+            } else {
+                edit_distance(name, &candidate)
+            };
+            (distance <= max_distance).then_some((distance, candidate))
+        })
+        .min()
+        .map(|(_, candidate)| candidate)
+}
+
+/// The Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut previous_row = (0..=b.len()).collect::<Vec<_>>();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut row = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous_row[j] + usize::from(a_char != *b_char);
+            row.push(substitution.min(previous_row[j + 1] + 1).min(row[j] + 1));
+        }
+        previous_row = row;
+    }
+    previous_row[b.len()]
+}
@@ -0,0 +1,23 @@
+//! The `debug` container option, printing the code generated for a conversion while compiling.
+use std::fmt::Display;
+
+use proc_macro2::TokenStream;
+
+/// Prints the generated code on the standard error, which cargo displays with the compiler
+/// messages.
+/// The code is pretty-printed, unless it is not a valid file, which would be reported by the
+/// compiler anyway.
+pub fn print_expansion(
+    derive: &str,
+    source_enum: &impl Display,
+    target_enum: &impl Display,
+    output: &TokenStream,
+) {
+    let code = syn::parse2(output.clone())
+        .map(|file| prettyplease::unparse(&file))
+        .unwrap_or_else(|_| output.to_string());
+    eprintln!(
+        "// #[derive({derive})] conversion from `{source_enum}` into `{target_enum}`\n{}",
+        code.trim_end()
+    );
+}
@@ -0,0 +1,206 @@
+//! The dump of the derived conversions as JSON and Graphviz files, written in the directory of
+//! the `ENUM_CONVERT_DUMP_DIR` environment variable when it is set.
+use std::{fmt::Write as _, fs, io, path::Path};
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+
+use enum_convert_core::idents::ContainerIdent;
+
+use crate::mapping::{MappedField, MappedVariant};
+
+pub const DUMP_DIR_VAR: &str = "ENUM_CONVERT_DUMP_DIR";
+
+/// Writes the conversion from the source enum into the target enum in the dump directory, if any.
+/// The generated code reads the environment variable, so that the compiler tracks it and the
+/// conversions are dumped again when it changes.
+pub fn dump_mapping(
+    source_enum: &ContainerIdent,
+    target_enum: &ContainerIdent,
+    variants: &[MappedVariant],
+) -> TokenStream {
+    let tracking = quote! {
+        const _: ::core::option::Option<&str> = ::core::option_env!(#DUMP_DIR_VAR);
+    };
+    let Some(dir) = std::env::var_os(DUMP_DIR_VAR) else {
+        return tracking;
+    };
+    let crate_name = std::env::var("CARGO_CRATE_NAME").unwrap_or_default();
+    let conversion = Conversion {
+        crate_name: &crate_name,
+        location: location_hash(),
+        source_enum: source_enum.to_string(),
+        target_enum: target_enum.to_string(),
+        variants,
+    };
+    match conversion.write(Path::new(&dir)) {
+        Ok(()) => tracking,
+        Err(err) => {
+            let error = syn::Error::new(
+                Span::call_site(),
+                format!(
+                    "cannot dump the conversion into `{}`: {err}",
+                    Path::new(&dir).display()
+                ),
+            )
+            .into_compile_error();
+            quote! {
+                #tracking
+                #error
+            }
+        }
+    }
+}
+
+struct Conversion<'a> {
+    crate_name: &'a str,
+    location: u64,
+    source_enum: String,
+    target_enum: String,
+    variants: &'a [MappedVariant],
+}
+
+impl Conversion<'_> {
+    fn write(&self, dir: &Path) -> io::Result<()> {
+        fs::create_dir_all(dir)?;
+        let name = format!(
+            "{}.{}.{}.{:016x}",
+            self.crate_name, self.source_enum, self.target_enum, self.location
+        );
+        fs::write(dir.join(format!("{name}.json")), self.json())?;
+        fs::write(dir.join(format!("{name}.dot")), self.dot())
+    }
+
+    fn json(&self) -> String {
+        let variants = self
+            .variants
+            .iter()
+            .map(|variant| {
+                let target = variant
+                    .target_name()
+                    .map_or_else(|| "null".to_string(), |target| json_string(&target));
+                let fields = variant
+                    .fields
+                    .iter()
+                    .map(|MappedField { source, target }| {
+                        format!(
+                            "{{ \"source\": {}, \"target\": {} }}",
+                            json_string(source),
+                            json_string(target)
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!(
+                    "    {{ \"source\": {}, \"target\": {target}, \"fields\": [{fields}] }}",
+                    json_string(&variant.source)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",\n");
+        format!(
+            "{{\n  \"crate\": {},\n  \"source\": {},\n  \"target\": {},\n  \"variants\": [\n{variants}\n  ]\n}}\n",
+            json_string(self.crate_name),
+            json_string(&self.source_enum),
+            json_string(&self.target_enum),
+        )
+    }
+
+    /// A graph with a cluster per enum, whose variants are linked by the conversions.
+    /// The graphs of several conversions can be merged with `gvpack -u`.
+    fn dot(&self) -> String {
+        let source_enum = format!("{}::{}", self.crate_name, self.source_enum);
+        let target_enum = format!("{}::{}", self.crate_name, self.target_enum);
+        let mut dot = format!(
+            "digraph {} {{\n",
+            dot_string(&format!("{source_enum} -> {target_enum}"))
+        );
+        let mut source_variants = Vec::new();
+        let mut target_variants = Vec::new();
+        let mut edges = String::new();
+        for variant in self.variants {
+            let Some(target) = variant.target_name() else {
+                continue;
+            };
+            let label = variant
+                .fields
+                .iter()
+                .map(|MappedField { source, target }| {
+                    if source == target {
+                        source.clone()
+                    } else {
+                        format!("{source} -> {target}")
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("\\n");
+            let _ = writeln!(
+                edges,
+                "  {} -> {} [label={}];",
+                dot_string(&format!("{source_enum}::{}", variant.source)),
+                dot_string(&format!("{target_enum}::{target}")),
+                dot_string(&label)
+            );
+            if !source_variants.contains(&variant.source) {
+                source_variants.push(variant.source.clone());
+            }
+            if !target_variants.contains(&target) {
+                target_variants.push(target);
+            }
+        }
+        for (enum_name, variants) in [
+            (&source_enum, source_variants),
+            (&target_enum, target_variants),
+        ] {
+            let _ = writeln!(
+                dot,
+                "  subgraph {} {{\n    label={};",
+                dot_string(&format!("cluster_{enum_name}")),
+                dot_string(enum_name)
+            );
+            for variant in variants {
+                let _ = writeln!(
+                    dot,
+                    "    {} [label={}];",
+                    dot_string(&format!("{enum_name}::{variant}")),
+                    dot_string(&variant)
+                );
+            }
+            dot.push_str("  }\n");
+        }
+        dot.push_str(&edges);
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// A hash of the location of the annotated enum, which tells apart the conversions between the
+/// enums of the same names in different modules.
+fn location_hash() -> u64 {
+    let span = proc_macro::Span::call_site();
+    let location = format!("{}:{}:{}", span.file(), span.line(), span.column());
+    // FNV-1a, which does not depend on the version of the compiler unlike `DefaultHasher`.
+    location.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+fn json_string(value: &str) -> String {
+    let mut string = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => string.push_str("\\\""),
+            '\\' => string.push_str("\\\\"),
+            c if c.is_control() => {
+                let _ = write!(string, "\\u{:04x}", c as u32);
+            }
+            c => string.push(c),
+        }
+    }
+    string.push('"');
+    string
+}
+
+fn dot_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('"', "\\\""))
+}
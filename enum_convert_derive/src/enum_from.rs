@@ -0,0 +1,21 @@
+use proc_macro2::TokenStream;
+
+use enum_convert_core::enum_from::{EnumFromMapping, ParsedEnumFrom};
+
+mod generator;
+
+pub fn derive_enum_from_impl(input: TokenStream) -> TokenStream {
+    ParsedEnumFrom::parse(input)
+        .and_then(EnumFromMapping::try_from)
+        .and_then(EnumFromMapping::check_sync)
+        .map(generator::generate)
+        .unwrap_or_else(|err| err.into_compile_error())
+}
+
+pub fn derive_enum_from_async_impl(input: TokenStream) -> TokenStream {
+    ParsedEnumFrom::parse(input)
+        .and_then(EnumFromMapping::try_from)
+        .and_then(EnumFromMapping::check_async)
+        .map(generator::generate_async)
+        .unwrap_or_else(|err| err.into_compile_error())
+}
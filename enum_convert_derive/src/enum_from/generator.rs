@@ -0,0 +1,781 @@
+use std::collections::BTreeMap;
+
+use enum_convert_core::{
+    enum_from::{
+        EnumFromMapping,
+        mapping::{
+            IntegerPattern, IntegerSource, SourceEnum, TargetVariant, VariantArm, VariantMapping,
+            VariantsMapping,
+        },
+        parser::ContainerOptions,
+    },
+    errors::sorted_by_name,
+    idents::{ContainerIdent, FieldIdent, FieldRef, VariantIdent},
+};
+use indexmap::IndexMap;
+use proc_macro2::TokenStream;
+use quote::{ToTokens, quote, quote_spanned};
+use syn::{
+    Expr, ExprCall, ExprClosure, ExprPath, Fields, Ident, Index, PatIdent,
+    visit::{self, Visit},
+};
+
+use crate::{
+    debug::print_expansion,
+    mapping::{MappedField, MappedVariant, generate_mapping_impl},
+    registry::{ExpectedFields, ExpectedVariant, ShapeCheck},
+    types,
+};
+
+/// Whether the generated conversion is a `From` or a `FromAsync` implementation.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Asyncness {
+    Sync,
+    Async,
+}
+
+/// Generates the `From` implementations of the mapping.
+pub fn generate(mapping: EnumFromMapping) -> TokenStream {
+    generate_impls(mapping, Asyncness::Sync)
+}
+
+/// Generates the `FromAsync` implementations of the mapping.
+pub fn generate_async(mapping: EnumFromMapping) -> TokenStream {
+    generate_impls(mapping, Asyncness::Async)
+}
+
+fn generate_impls(mapping: EnumFromMapping, asyncness: Asyncness) -> TokenStream {
+    let target_enum = &mapping.target_enum;
+    let target_variants = &mapping.target_variants;
+    let discriminants = &mapping.discriminants;
+    let integer_impl_blocks = mapping
+        .integer_sources
+        .iter()
+        .map(|integer_source| generate_try_from_integer_impl(integer_source, target_enum));
+
+    let impl_blocks = mapping
+        .source_enums
+        .into_iter()
+        .map(
+            |(
+                source_enum,
+                SourceEnum {
+                    options,
+                    declared_variants,
+                    variants_mapping,
+                },
+            )| {
+                let expected_variants = options.registered.then(|| {
+                    if options.by_discriminant {
+                        declared_variants
+                            .iter()
+                            .flatten()
+                            .map(|declared_variant| ExpectedVariant {
+                                ident: declared_variant.0.clone(),
+                                fields: ExpectedFields::Unit,
+                            })
+                            .collect()
+                    } else {
+                        expected_variants(&variants_mapping, target_variants)
+                    }
+                });
+                let mapped_variants = if options.by_discriminant {
+                    discriminant_mapped_variants(
+                        &source_enum,
+                        declared_variants.as_deref().unwrap_or_default(),
+                        discriminants,
+                    )
+                } else {
+                    mapped_variants(&variants_mapping, target_variants)
+                };
+                let mapping_impl = generate_mapping_impl(
+                    &source_enum,
+                    target_enum,
+                    mapped_variants,
+                    options.mapping.then(|| match asyncness {
+                        Asyncness::Sync => quote! { ::enum_convert::EnumMapping },
+                        Asyncness::Async => quote! { ::enum_convert::EnumMappingAsync },
+                    }),
+                    // The targets of the registered variants are only known once their
+                    // discriminants are.
+                    !(options.by_discriminant && options.registered),
+                );
+                // A catch-all arm would also hide the values of the declared variants which are
+                // only converted by refined arms from the exhaustiveness check.
+                let converts_all_declared_values = variants_mapping
+                    .0
+                    .values()
+                    .all(|variant_arms| variant_arms.iter().any(|arm| !arm.is_refined()));
+                let mut match_arms = if options.by_discriminant {
+                    generate_discriminant_match_arms(
+                        &source_enum,
+                        declared_variants.as_deref().unwrap_or_default(),
+                        target_enum,
+                        discriminants,
+                    )
+                } else {
+                    generate_match_arms(
+                        &source_enum,
+                        &options,
+                        variants_mapping,
+                        target_enum,
+                        target_variants,
+                        asyncness,
+                    )
+                };
+                let assertion = declared_variants.map(|declared_variants| {
+                    // The assertion reports the missing variants, they do not have to be
+                    // reported by the conversion too.
+                    if converts_all_declared_values {
+                        match_arms.push(quote! {
+                            #[allow(unreachable_patterns)]
+                            _ => ::core::unreachable!(),
+                        });
+                    }
+                    generate_declared_variants_assertion(&source_enum, &declared_variants)
+                });
+                let debug = options.debug;
+                let by_discriminant = options.by_discriminant;
+                let impl_block = match asyncness {
+                    Asyncness::Sync => generate_from_impl(&source_enum, target_enum, match_arms),
+                    Asyncness::Async => {
+                        generate_from_async_impl(&source_enum, target_enum, options, match_arms)
+                    }
+                };
+                let output = quote! {
+                    #assertion
+                    #impl_block
+                    #mapping_impl
+                };
+                if debug {
+                    let derive = match asyncness {
+                        Asyncness::Sync => "EnumFrom",
+                        Asyncness::Async => "EnumFromAsync",
+                    };
+                    print_expansion(derive, &source_enum, target_enum, &output);
+                }
+                match expected_variants {
+                    // The generated code is only emitted once checked against the registered
+                    // source enum.
+                    Some(variants) => ShapeCheck {
+                        registered_enum: source_enum.0.clone(),
+                        annotated_enum: target_enum.0.clone(),
+                        exhaustive: true,
+                        variants,
+                        discriminants: by_discriminant.then(|| {
+                            discriminants
+                                .iter()
+                                .map(|(target_variant, discriminant)| {
+                                    (target_variant.0.clone(), discriminant.clone())
+                                })
+                                .collect()
+                        }),
+                        output,
+                    }
+                    .generate(),
+                    None => output,
+                }
+            },
+        )
+        .collect::<Vec<_>>();
+
+    quote! {
+        #(#impl_blocks)*
+        #(#integer_impl_blocks)*
+    }
+}
+
+/// The source variants matched by the arms, and their fields bound by the arms which do not use a
+/// pattern.
+fn expected_variants(
+    variants_mapping: &VariantsMapping,
+    target_variants: &IndexMap<VariantIdent, TargetVariant>,
+) -> Vec<ExpectedVariant> {
+    sorted_by_name(&variants_mapping.0)
+        .into_iter()
+        .flat_map(|(source_variant, variant_arms)| {
+            variant_arms.iter().map(|variant_arm| ExpectedVariant {
+                ident: source_variant.0.clone(),
+                fields: if variant_arm.has_pattern() {
+                    ExpectedFields::Unknown
+                } else {
+                    expected_fields(&variant_arm.variant_mapping, target_variants)
+                },
+            })
+        })
+        .collect()
+}
+
+fn expected_fields(
+    variant_mapping: &VariantMapping,
+    target_variants: &IndexMap<VariantIdent, TargetVariant>,
+) -> ExpectedFields {
+    let target_fields = target_variants
+        .get(variant_mapping.target_variant())
+        .map(|target_variant| &target_variant.variant.fields);
+    match variant_mapping {
+        VariantMapping::UnitToUnit { .. } => ExpectedFields::Unit,
+        VariantMapping::PatternToUnit { .. } => ExpectedFields::Unknown,
+        VariantMapping::TupleToTuple { fields_mapping, .. } => ExpectedFields::Tuple(
+            (0..target_fields.map_or(0, Fields::len))
+                .map(|target_pos| *fields_mapping.get(&target_pos).unwrap_or(&target_pos))
+                .collect(),
+        ),
+        VariantMapping::TupleToStruct { fields_mapping, .. } => {
+            ExpectedFields::Tuple(fields_mapping.values().copied().collect())
+        }
+        VariantMapping::StructToStruct { fields_mapping, .. } => ExpectedFields::Struct(
+            target_fields
+                .into_iter()
+                .flatten()
+                .filter_map(|field| field.ident.clone())
+                .map(|target_ident| {
+                    let target_field = FieldIdent(target_ident);
+                    fields_mapping
+                        .get(&target_field)
+                        .unwrap_or(&target_field)
+                        .0
+                        .clone()
+                })
+                .collect(),
+        ),
+        VariantMapping::StructToTuple { fields_mapping, .. } => ExpectedFields::Struct(
+            fields_mapping
+                .iter()
+                .collect::<BTreeMap<_, _>>()
+                .into_values()
+                .map(|source_field| source_field.0.clone())
+                .collect(),
+        ),
+    }
+}
+
+/// The conversions of the source variants, and the source fields the target fields are converted
+/// from when they are not matched by a pattern.
+fn mapped_variants(
+    variants_mapping: &VariantsMapping,
+    target_variants: &IndexMap<VariantIdent, TargetVariant>,
+) -> Vec<MappedVariant> {
+    sorted_by_name(&variants_mapping.0)
+        .into_iter()
+        .flat_map(|(source_variant, variant_arms)| {
+            variant_arms.iter().map(move |variant_arm| {
+                let variant_mapping = &variant_arm.variant_mapping;
+                let fields = if variant_arm.has_pattern() {
+                    Vec::new()
+                } else {
+                    mapped_fields(variant_mapping, target_variants)
+                };
+                MappedVariant::new(source_variant, variant_mapping.target_variant(), fields)
+            })
+        })
+        .collect()
+}
+
+fn mapped_fields(
+    variant_mapping: &VariantMapping,
+    target_variants: &IndexMap<VariantIdent, TargetVariant>,
+) -> Vec<MappedField> {
+    let target_fields = target_variants
+        .get(variant_mapping.target_variant())
+        .map(|target_variant| &target_variant.variant.fields);
+    match variant_mapping {
+        VariantMapping::UnitToUnit { .. } | VariantMapping::PatternToUnit { .. } => Vec::new(),
+        VariantMapping::TupleToTuple { fields_mapping, .. } => (0..target_fields
+            .map_or(0, Fields::len))
+            .map(|target_pos| {
+                MappedField::new(
+                    fields_mapping.get(&target_pos).unwrap_or(&target_pos),
+                    target_pos,
+                )
+            })
+            .collect(),
+        VariantMapping::TupleToStruct { fields_mapping, .. } => fields_mapping
+            .iter()
+            .map(|(target_field, source_pos)| (source_pos, target_field))
+            .collect::<BTreeMap<_, _>>()
+            .into_iter()
+            .map(|(source_pos, target_field)| MappedField::new(source_pos, target_field))
+            .collect(),
+        VariantMapping::StructToStruct { fields_mapping, .. } => target_fields
+            .into_iter()
+            .flatten()
+            .filter_map(|field| field.ident.clone())
+            .map(|target_ident| {
+                let target_field = FieldIdent(target_ident);
+                MappedField::new(
+                    fields_mapping.get(&target_field).unwrap_or(&target_field),
+                    &target_field,
+                )
+            })
+            .collect(),
+        VariantMapping::StructToTuple { fields_mapping, .. } => fields_mapping
+            .iter()
+            .collect::<BTreeMap<_, _>>()
+            .into_iter()
+            .map(|(target_pos, source_field)| MappedField::new(source_field, target_pos))
+            .collect(),
+    }
+}
+
+/// The conversions of the declared source variants, whose target variants are looked up at compile
+/// time.
+fn discriminant_mapped_variants(
+    source_enum: &ContainerIdent,
+    declared_variants: &[VariantIdent],
+    discriminants: &[(VariantIdent, TokenStream)],
+) -> Vec<MappedVariant> {
+    let (target_variants, discriminants): (Vec<_>, Vec<_>) = discriminants
+        .iter()
+        .map(|(target_variant, discriminant)| (target_variant.to_string(), discriminant))
+        .unzip();
+    declared_variants
+        .iter()
+        .map(|source_variant| MappedVariant {
+            source: source_variant.to_string(),
+            target: quote! {
+                {
+                    const DISCRIMINANT: isize = #source_enum::#source_variant as isize;
+                    #(if DISCRIMINANT == (#discriminants) as isize {
+                        #target_variants
+                    } else)* {
+                        ""
+                    }
+                }
+            },
+            fields: Vec::new(),
+        })
+        .collect()
+}
+
+/// Generates a match on the declared variants, which fails to compile when some variants of the
+/// source enum are not declared.
+fn generate_declared_variants_assertion(
+    source_enum: &ContainerIdent,
+    declared_variants: &[VariantIdent],
+) -> TokenStream {
+    let value = quote_spanned! { source_enum.0.span()=> value };
+    quote! {
+        const _: () = {
+            #[allow(dead_code)]
+            fn assert_declared_variants(value: &#source_enum) {
+                match #value {
+                    #(#source_enum::#declared_variants { .. } => {})*
+                }
+            }
+        };
+    }
+}
+
+fn generate_from_impl(
+    source_enum: &ContainerIdent,
+    target_enum: &ContainerIdent,
+    match_arms: Vec<TokenStream>,
+) -> TokenStream {
+    quote! {
+        #[automatically_derived]
+        impl ::core::convert::From<#source_enum> for #target_enum {
+            fn from(value: #source_enum) -> Self {
+                match value {
+                    #(#match_arms)*
+                }
+            }
+        }
+    }
+}
+
+fn generate_try_from_integer_impl(
+    IntegerSource { integer_type, arms }: &IntegerSource,
+    target_enum: &ContainerIdent,
+) -> TokenStream {
+    let arms = arms.iter().map(|(target_variant, pattern)| {
+        let pattern = match pattern {
+            IntegerPattern::Patterns(patterns) => quote! { #(#patterns)|* },
+            IntegerPattern::Discriminant(discriminant) => {
+                quote! { value if value == (#discriminant) as #integer_type }
+            }
+        };
+        quote! { #pattern => ::core::result::Result::Ok(Self::#target_variant), }
+    });
+    quote! {
+        #[automatically_derived]
+        impl ::core::convert::TryFrom<#integer_type> for #target_enum {
+            type Error = #integer_type;
+
+            fn try_from(value: #integer_type) -> ::core::result::Result<Self, Self::Error> {
+                match value {
+                    #(#arms)*
+                    _ => ::core::result::Result::Err(value),
+                }
+            }
+        }
+    }
+}
+
+fn generate_from_async_impl(
+    source_enum: &ContainerIdent,
+    target_enum: &ContainerIdent,
+    options: ContainerOptions,
+    match_arms: Vec<TokenStream>,
+) -> TokenStream {
+    let error = options
+        .error
+        .map(|error| quote! { #error })
+        .unwrap_or_else(|| quote! { ::core::convert::Infallible });
+
+    quote! {
+        #[automatically_derived]
+        impl ::enum_convert::FromAsync<#source_enum> for #target_enum {
+            type Error = #error;
+
+            async fn from_async(value: #source_enum) -> ::core::result::Result<Self, Self::Error> {
+                ::core::result::Result::Ok(match value {
+                    #(#match_arms)*
+                })
+            }
+        }
+    }
+}
+
+/// Generates the arms converting each declared source variant into the target variant with the
+/// same discriminant, which is looked up at compile time.
+fn generate_discriminant_match_arms(
+    source_enum: &ContainerIdent,
+    declared_variants: &[VariantIdent],
+    target_enum: &ContainerIdent,
+    discriminants: &[(VariantIdent, TokenStream)],
+) -> Vec<TokenStream> {
+    let (target_variants, discriminants): (Vec<_>, Vec<_>) = discriminants.iter().cloned().unzip();
+    declared_variants
+        .iter()
+        .map(|source_variant| {
+            let message = format!(
+                "`{source_enum}::{source_variant}` has no variant with the same discriminant in `{target_enum}`"
+            );
+            let assertion = quote_spanned! { source_variant.0.span()=>
+                const _: () = ::core::assert!(
+                    false #(|| DISCRIMINANT == (#discriminants) as isize)*,
+                    #message
+                );
+            };
+            quote! {
+                #source_enum::#source_variant => {
+                    const DISCRIMINANT: isize = #source_enum::#source_variant as isize;
+                    #assertion
+                    #(if DISCRIMINANT == (#discriminants) as isize {
+                        #target_enum::#target_variants
+                    } else)* {
+                        ::core::unreachable!()
+                    }
+                }
+            }
+        })
+        .collect()
+}
+
+fn generate_match_arms(
+    source_enum: &ContainerIdent,
+    options: &ContainerOptions,
+    variants_mapping: VariantsMapping,
+    target_enum: &ContainerIdent,
+    target_variants: &IndexMap<VariantIdent, TargetVariant>,
+    asyncness: Asyncness,
+) -> Vec<TokenStream> {
+    variants_mapping
+        .0
+        .into_iter()
+        .flat_map(|(source_variant, variant_arms)| {
+            variant_arms
+                .into_iter()
+                .map(|variant_arm| {
+                    let target_variant = target_variants
+                        .get(variant_arm.variant_mapping.target_variant())
+                        .expect(
+                            "All target variants in variant_mapping should be present in target_variants",
+                        );
+                    generate_match_arm(
+                        &source_variant,
+                        variant_arm,
+                        source_enum,
+                        options,
+                        target_enum,
+                        target_variant,
+                        asyncness,
+                    )
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Generates the expression converting `value` into the type of the target field.
+fn generate_field_conversion(
+    value: impl ToTokens,
+    target_field: &FieldRef,
+    container_options: &ContainerOptions,
+    target_enum: &ContainerIdent,
+    TargetVariant {
+        variant,
+        fields_options,
+    }: &TargetVariant,
+    asyncness: Asyncness,
+) -> TokenStream {
+    let field_options = fields_options.get(target_field);
+    let with = field_options.and_then(|options| options.with.as_ref());
+    let map = field_options.is_some_and(|options| options.map);
+    if with.is_none()
+        && ((container_options.as_is && !map) || field_options.is_some_and(|options| options.as_is))
+    {
+        return quote! { #value };
+    }
+    let ty = types::field_type(&variant.fields, target_field);
+    match (with, asyncness, ty) {
+        (Some(with), Asyncness::Async, _) => quote! { #with(#value).await? },
+        // The other values of the source enum are converted with `FromAsync` too.
+        (None, Asyncness::Async, Some(ty)) if map && types::contains_self(ty, target_enum) => {
+            types::generate_async_conversion(value, ty, target_enum)
+        }
+        (_, _, Some(ty)) => types::generate_conversion(value, ty, target_enum, map),
+        (_, _, None) => quote! { ::core::convert::Into::into(#value) },
+    }
+}
+
+fn generate_match_arm(
+    source_variant: &VariantIdent,
+    VariantArm {
+        guard,
+        inner_variants,
+        variant_mapping,
+        ..
+    }: VariantArm,
+    source_enum: &ContainerIdent,
+    options: &ContainerOptions,
+    target_enum: &ContainerIdent,
+    target_variant: &TargetVariant,
+    asyncness: Asyncness,
+) -> TokenStream {
+    let convert = |value: &dyn ToTokens, target_field: FieldRef| {
+        generate_field_conversion(
+            value,
+            &target_field,
+            options,
+            target_enum,
+            target_variant,
+            asyncness,
+        )
+    };
+    let variant = &target_variant.variant;
+    let guard_bindings = guard.as_ref().map(generate_guard_bindings);
+    let guard = guard.map(|guard| quote! { if #guard });
+    // The fields of struct source variants may not follow the naming conventions.
+    let lints = matches!(
+        variant_mapping,
+        VariantMapping::StructToStruct { .. } | VariantMapping::StructToTuple { .. }
+    )
+    .then(|| quote! { #[allow(non_snake_case)] });
+    // The fields are matched in the innermost variant, wrapped in the other ones.
+    let mut wrappers = inner_variants;
+    wrappers.insert(0, (source_enum.clone(), source_variant.clone()));
+    let (source_enum, source_variant) = wrappers
+        .pop()
+        .expect("wrappers should contain at least the source variant");
+    let (pattern, expression) = match variant_mapping {
+        VariantMapping::UnitToUnit { target_variant } => (
+            match guard_bindings {
+                Some(bindings) => quote! { #source_enum::#source_variant { #(#bindings,)* .. } },
+                None => quote! { #source_enum::#source_variant },
+            },
+            quote! { #target_enum::#target_variant },
+        ),
+        VariantMapping::PatternToUnit {
+            target_variant,
+            payload,
+        } => (
+            quote! { #source_enum::#source_variant #payload },
+            quote! { #target_enum::#target_variant },
+        ),
+        VariantMapping::TupleToTuple {
+            target_variant,
+            fields_mapping,
+        } => {
+            let (source_fields, target_fields): (Vec<_>, Vec<_>) = (0..variant.fields.len())
+                .map(|field_pos| {
+                    let field_source_pos = fields_mapping.get(&field_pos).unwrap_or(&field_pos);
+                    let source_field_name = quote::format_ident!("field_{field_source_pos}");
+                    (
+                        quote::format_ident!("field_{field_pos}"),
+                        convert(&source_field_name, FieldRef::FieldPos(field_pos)),
+                    )
+                })
+                .unzip();
+            (
+                quote! { #source_enum::#source_variant(#(#source_fields),*) },
+                quote! { #target_enum::#target_variant(#(#target_fields),*) },
+            )
+        }
+        VariantMapping::StructToTuple {
+            target_variant,
+            fields_mapping,
+        } => {
+            let (source_fields, target_fields): (Vec<_>, Vec<_>) = (0..variant.fields.len())
+                .map(|field_target_pos| {
+                    let source_ident = fields_mapping
+                        .get(&field_target_pos)
+                        .expect("fields_mapping exhaustiveness should have been checked");
+                    (
+                        quote! { #source_ident },
+                        convert(source_ident, FieldRef::FieldPos(field_target_pos)),
+                    )
+                })
+                .unzip();
+            (
+                quote! { #source_enum::#source_variant { #(#source_fields),* } },
+                quote! { #target_enum::#target_variant(#(#target_fields),*) },
+            )
+        }
+        VariantMapping::StructToStruct {
+            target_variant,
+            fields_mapping,
+        } => {
+            let (source_fields, target_fields): (Vec<_>, Vec<_>) = variant
+                .fields
+                .iter()
+                .map(|field| {
+                    let target_field = FieldIdent(
+                        field
+                            .ident
+                            .as_ref()
+                            .expect("A named field should always have an ident")
+                            .clone(),
+                    );
+                    // The source field is bound to its own name, which the guards refer to.
+                    let source_field = fields_mapping.get(&target_field).unwrap_or(&target_field);
+                    let conversion =
+                        convert(source_field, FieldRef::FieldIdent(target_field.clone()));
+                    (
+                        quote! { #source_field },
+                        quote! { #target_field: #conversion },
+                    )
+                })
+                .unzip();
+
+            (
+                quote! { #source_enum::#source_variant { #(#source_fields),* } },
+                quote! { #target_enum::#target_variant { #(#target_fields),* } },
+            )
+        }
+        VariantMapping::TupleToStruct {
+            target_variant,
+            fields_mapping,
+        } => {
+            // The source fields are bound by position, as the guards refer to them.
+            let (source_fields, target_fields): (Vec<_>, Vec<_>) = fields_mapping
+                .into_iter()
+                .map(|(target_ident, source_pos)| (source_pos, target_ident))
+                .collect::<BTreeMap<usize, FieldIdent>>()
+                .into_iter()
+                .map(|(source_pos, target_ident)| {
+                    let source_field = quote::format_ident!("field_{source_pos}");
+                    let conversion =
+                        convert(&source_field, FieldRef::FieldIdent(target_ident.clone()));
+                    (
+                        quote! { #source_field },
+                        quote! { #target_ident: #conversion },
+                    )
+                })
+                .unzip();
+
+            (
+                quote! { #source_enum::#source_variant(#(#source_fields),*) },
+                quote! { #target_enum::#target_variant { #(#target_fields),* } },
+            )
+        }
+    };
+    let pattern =
+        wrappers
+            .into_iter()
+            .rev()
+            .fold(pattern, |pattern, (wrapper_enum, wrapper_variant)| {
+                quote! { #wrapper_enum::#wrapper_variant(#pattern) }
+            });
+
+    quote! { #lints #pattern #guard => #expression, }
+}
+
+/// Generates the bindings of the source fields a guard refers to, for the arms whose pattern does
+/// not bind them: `field_N` is the field at position N of a tuple variant, the other lowercase
+/// names are the fields of a struct variant.
+/// The fields of the source variant are not known, so the lowercase constants and statics have to
+/// be qualified with a path, or the compiler reports them as unknown fields on the guard.
+fn generate_guard_bindings(guard: &Expr) -> Vec<TokenStream> {
+    #[derive(Default)]
+    struct Bindings(Vec<Ident>);
+
+    impl<'ast> Visit<'ast> for Bindings {
+        fn visit_expr_path(&mut self, expr: &'ast ExprPath) {
+            let Some(ident) = expr.path.get_ident() else {
+                return;
+            };
+            let name = ident.to_string();
+            if expr.qself.is_none()
+                && name != "self"
+                && name.starts_with(|c: char| c.is_lowercase() || c == '_')
+                && !self.0.contains(ident)
+            {
+                self.0.push(ident.clone());
+            }
+        }
+
+        // The called functions are not fields.
+        fn visit_expr_call(&mut self, expr: &'ast ExprCall) {
+            if !matches!(*expr.func, Expr::Path(_)) {
+                self.visit_expr(&expr.func);
+            }
+            for arg in &expr.args {
+                self.visit_expr(arg);
+            }
+        }
+
+        // Nor are the parameters of the closures.
+        fn visit_expr_closure(&mut self, expr: &'ast ExprClosure) {
+            let mut parameters = Bindings::default();
+            for input in &expr.inputs {
+                visit::visit_pat(&mut parameters, input);
+            }
+            let mut body = Bindings::default();
+            body.visit_expr(&expr.body);
+            for ident in body.0 {
+                if !parameters.0.contains(&ident) && !self.0.contains(&ident) {
+                    self.0.push(ident);
+                }
+            }
+        }
+
+        fn visit_pat_ident(&mut self, pat: &'ast PatIdent) {
+            self.0.push(pat.ident.clone());
+        }
+    }
+
+    let mut bindings = Bindings::default();
+    bindings.visit_expr(guard);
+    bindings
+        .0
+        .into_iter()
+        .map(|ident| {
+            match ident
+                .to_string()
+                .strip_prefix("field_")
+                .and_then(|pos| pos.parse::<u32>().ok())
+            {
+                Some(pos) => {
+                    let index = Index {
+                        index: pos,
+                        span: ident.span(),
+                    };
+                    quote! { #index: #ident }
+                }
+                None => quote! { #ident },
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,12 @@
+use proc_macro2::TokenStream;
+
+use enum_convert_core::enum_into::{EnumIntoMapping, ParsedEnumInto};
+
+mod generator;
+
+pub fn derive_enum_into_impl(input: TokenStream) -> TokenStream {
+    ParsedEnumInto::parse(input)
+        .and_then(EnumIntoMapping::try_from)
+        .map(generator::generate)
+        .unwrap_or_else(|err| err.into_compile_error())
+}
@@ -0,0 +1,449 @@
+use std::collections::BTreeMap;
+
+use enum_convert_core::{
+    enum_into::{
+        EnumIntoMapping,
+        mapping::{
+            IntegerTarget, IntegerValue, SourceVariant, TargetEnum, VariantArm, VariantMapping,
+            VariantsMapping,
+        },
+        parser::ContainerOptions,
+    },
+    idents::{ContainerIdent, FieldIdent, FieldRef, VariantIdent},
+};
+use indexmap::IndexMap;
+use proc_macro2::TokenStream;
+use quote::{ToTokens, quote};
+use syn::Fields;
+
+use crate::{
+    debug::print_expansion,
+    mapping::{MappedField, MappedVariant, generate_mapping_impl},
+    registry::{ExpectedFields, ExpectedVariant, ShapeCheck},
+    types,
+};
+
+/// Generates the `From` implementations of the mapping.
+pub fn generate(mapping: EnumIntoMapping) -> TokenStream {
+    let source_enum = &mapping.source_enum;
+    let source_variants = &mapping.source_variants;
+    let integer_impl_blocks = mapping
+        .integer_targets
+        .iter()
+        .map(|integer_target| generate_from_integer_impl(integer_target, source_enum));
+
+    let impl_blocks = mapping
+        .target_enums
+        .into_iter()
+        .map(
+            |(
+                target_enum,
+                TargetEnum {
+                    options,
+                    variants_mapping,
+                },
+            )| {
+                let expected_variants = options
+                    .registered
+                    .then(|| expected_variants(&variants_mapping, source_variants));
+                let registered_enum = target_enum.0.clone();
+                let debug = options.debug;
+                let mapping_impl = generate_mapping_impl(
+                    source_enum,
+                    &target_enum,
+                    mapped_variants(&variants_mapping, source_variants),
+                    options
+                        .mapping
+                        .then(|| quote! { ::enum_convert::EnumMapping }),
+                    true,
+                );
+                let from_impl = generate_from_impl(
+                    target_enum,
+                    options,
+                    variants_mapping,
+                    source_enum,
+                    source_variants,
+                );
+                let output = quote! {
+                    #from_impl
+                    #mapping_impl
+                };
+                if debug {
+                    print_expansion("EnumInto", source_enum, &registered_enum, &output);
+                }
+                match expected_variants {
+                    // The generated code is only emitted once checked against the registered
+                    // target enum.
+                    Some(variants) => ShapeCheck {
+                        registered_enum,
+                        annotated_enum: source_enum.0.clone(),
+                        exhaustive: false,
+                        variants,
+                        discriminants: None,
+                        output,
+                    }
+                    .generate(),
+                    None => output,
+                }
+            },
+        )
+        .collect::<Vec<_>>();
+
+    quote! {
+        #(#impl_blocks)*
+        #(#integer_impl_blocks)*
+    }
+}
+
+/// The target variants built by the arms, and their fields.
+fn expected_variants(
+    variants_mapping: &VariantsMapping,
+    source_variants: &IndexMap<VariantIdent, SourceVariant>,
+) -> Vec<ExpectedVariant> {
+    let mut variants_mapping = variants_mapping.0.iter().collect::<Vec<_>>();
+    variants_mapping.sort_by_key(|(target_variant, _)| target_variant.to_string());
+    variants_mapping
+        .into_iter()
+        .flat_map(|(target_variant, variant_arms)| {
+            variant_arms.iter().map(|variant_arm| ExpectedVariant {
+                ident: target_variant.0.clone(),
+                fields: if variant_arm.inner_variants.is_empty() {
+                    expected_fields(&variant_arm.variant_mapping, source_variants)
+                } else {
+                    // The variant wraps the variant of another enum.
+                    ExpectedFields::Tuple(vec![0])
+                },
+            })
+        })
+        .collect()
+}
+
+fn expected_fields(
+    variant_mapping: &VariantMapping,
+    source_variants: &IndexMap<VariantIdent, SourceVariant>,
+) -> ExpectedFields {
+    let source_fields = source_variants
+        .get(variant_mapping.source_variant())
+        .map(|source_variant| &source_variant.variant.fields);
+    match variant_mapping {
+        VariantMapping::UnitToUnit { .. } => ExpectedFields::Unit,
+        VariantMapping::TupleToTuple { fields_mapping, .. } => ExpectedFields::Tuple(
+            (0..source_fields.map_or(0, Fields::len))
+                .map(|source_pos| *fields_mapping.get(&source_pos).unwrap_or(&source_pos))
+                .collect(),
+        ),
+        VariantMapping::TupleToStruct { fields_mapping, .. } => ExpectedFields::Struct(
+            fields_mapping
+                .iter()
+                .collect::<BTreeMap<_, _>>()
+                .into_values()
+                .map(|target_field| target_field.0.clone())
+                .collect(),
+        ),
+        VariantMapping::StructToStruct { fields_mapping, .. } => ExpectedFields::Struct(
+            source_fields
+                .into_iter()
+                .flatten()
+                .filter_map(|field| field.ident.clone())
+                .map(|source_ident| {
+                    let source_field = FieldIdent(source_ident);
+                    fields_mapping
+                        .get(&source_field)
+                        .unwrap_or(&source_field)
+                        .0
+                        .clone()
+                })
+                .collect(),
+        ),
+        VariantMapping::StructToTuple { fields_mapping, .. } => {
+            ExpectedFields::Tuple(fields_mapping.values().copied().collect())
+        }
+    }
+}
+
+/// The conversions of the source variants, and the target fields the source fields are converted
+/// into when they are not wrapped in other variants.
+fn mapped_variants(
+    variants_mapping: &VariantsMapping,
+    source_variants: &IndexMap<VariantIdent, SourceVariant>,
+) -> Vec<MappedVariant> {
+    variants_mapping
+        .0
+        .iter()
+        .flat_map(|(target_variant, variant_arms)| {
+            variant_arms.iter().map(move |variant_arm| {
+                let variant_mapping = &variant_arm.variant_mapping;
+                let fields = if variant_arm.inner_variants.is_empty() {
+                    mapped_fields(variant_mapping, source_variants)
+                } else {
+                    Vec::new()
+                };
+                MappedVariant::new(variant_mapping.source_variant(), target_variant, fields)
+            })
+        })
+        .collect()
+}
+
+fn mapped_fields(
+    variant_mapping: &VariantMapping,
+    source_variants: &IndexMap<VariantIdent, SourceVariant>,
+) -> Vec<MappedField> {
+    let source_fields = source_variants
+        .get(variant_mapping.source_variant())
+        .map(|source_variant| &source_variant.variant.fields);
+    match variant_mapping {
+        VariantMapping::UnitToUnit { .. } => Vec::new(),
+        VariantMapping::TupleToTuple { fields_mapping, .. } => (0..source_fields
+            .map_or(0, Fields::len))
+            .map(|source_pos| {
+                MappedField::new(
+                    source_pos,
+                    fields_mapping.get(&source_pos).unwrap_or(&source_pos),
+                )
+            })
+            .collect(),
+        VariantMapping::TupleToStruct { fields_mapping, .. } => fields_mapping
+            .iter()
+            .collect::<BTreeMap<_, _>>()
+            .into_iter()
+            .map(|(source_pos, target_field)| MappedField::new(source_pos, target_field))
+            .collect(),
+        VariantMapping::StructToStruct { fields_mapping, .. } => source_fields
+            .into_iter()
+            .flatten()
+            .filter_map(|field| field.ident.clone())
+            .map(|source_ident| {
+                let source_field = FieldIdent(source_ident);
+                MappedField::new(
+                    &source_field,
+                    fields_mapping.get(&source_field).unwrap_or(&source_field),
+                )
+            })
+            .collect(),
+        VariantMapping::StructToTuple { fields_mapping, .. } => fields_mapping
+            .iter()
+            .map(|(source_field, target_pos)| (target_pos, source_field))
+            .collect::<BTreeMap<_, _>>()
+            .into_iter()
+            .map(|(target_pos, source_field)| MappedField::new(source_field, target_pos))
+            .collect(),
+    }
+}
+
+fn generate_from_integer_impl(
+    IntegerTarget {
+        integer_type,
+        values,
+    }: &IntegerTarget,
+    source_enum: &ContainerIdent,
+) -> TokenStream {
+    let arms = values.iter().map(|(source_variant, value)| {
+        let value = match value {
+            IntegerValue::Value(value) => value.into_token_stream(),
+            IntegerValue::Discriminant(discriminant) => quote! { (#discriminant) as #integer_type },
+        };
+        quote! { #source_enum::#source_variant => #value, }
+    });
+    quote! {
+        #[automatically_derived]
+        impl ::core::convert::From<#source_enum> for #integer_type {
+            fn from(value: #source_enum) -> Self {
+                match value {
+                    #(#arms)*
+                }
+            }
+        }
+    }
+}
+
+fn generate_from_impl(
+    target_enum: ContainerIdent,
+    options: ContainerOptions,
+    variants_mapping: VariantsMapping,
+    source_enum: &ContainerIdent,
+    source_variants: &IndexMap<VariantIdent, SourceVariant>,
+) -> TokenStream {
+    let match_arms = variants_mapping
+        .0
+        .into_iter()
+        .flat_map(|(target_variant, variant_arms)| {
+            variant_arms.into_iter().map(|VariantArm { inner_variants, variant_mapping }| {
+                let source_variant = source_variants.get(variant_mapping.source_variant()).expect(
+                    "All source variants in variant_mapping should be present in source_variants",
+                );
+                let (constructor_enum, constructor_variant) = inner_variants
+                    .last()
+                    .map_or((&target_enum, &target_variant), |(enum_ident, variant_ident)| {
+                        (enum_ident, variant_ident)
+                    });
+                let (pattern, expression) = generate_match_arm(
+                    constructor_variant,
+                    variant_mapping,
+                    constructor_enum,
+                    source_enum,
+                    &options,
+                    source_variant,
+                );
+                let expression = std::iter::once((&target_enum, &target_variant))
+                    .chain(inner_variants.iter().map(|(enum_ident, variant_ident)| (enum_ident, variant_ident)))
+                    .rev()
+                    .skip(1)
+                    .fold(expression, |expression, (enum_ident, variant_ident)| {
+                        quote! { #enum_ident::#variant_ident(#expression) }
+                    });
+                quote! { #pattern => #expression, }
+            }).collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    quote! {
+        #[automatically_derived]
+        impl ::core::convert::From<#source_enum> for #target_enum {
+            fn from(value: #source_enum) -> Self {
+                match value {
+                    #(#match_arms)*
+                }
+            }
+        }
+    }
+}
+
+/// Generates the expression converting `value` from the type of the source field.
+fn generate_field_conversion(
+    value: impl ToTokens,
+    source_field: &FieldRef,
+    source_enum: &ContainerIdent,
+    container_options: &ContainerOptions,
+    SourceVariant {
+        variant,
+        fields_options,
+    }: &SourceVariant,
+) -> TokenStream {
+    let field_options = fields_options.get(source_field);
+    let map = field_options.is_some_and(|options| options.map);
+    if (container_options.as_is && !map) || field_options.is_some_and(|options| options.as_is) {
+        return quote! { #value };
+    }
+    match types::field_type(&variant.fields, source_field) {
+        Some(ty) => types::generate_conversion(value, ty, source_enum, map),
+        None => quote! { ::core::convert::Into::into(#value) },
+    }
+}
+
+fn generate_match_arm(
+    target_variant: &VariantIdent,
+    variant_mapping: VariantMapping,
+    target_enum: &ContainerIdent,
+    source_enum: &ContainerIdent,
+    options: &ContainerOptions,
+    source_variant: &SourceVariant,
+) -> (TokenStream, TokenStream) {
+    let convert = |value: &dyn ToTokens, source_field: FieldRef| {
+        generate_field_conversion(value, &source_field, source_enum, options, source_variant)
+    };
+    let variant = &source_variant.variant;
+    match variant_mapping {
+        VariantMapping::UnitToUnit { source_variant } => (
+            quote! { #source_enum::#source_variant },
+            quote! { #target_enum::#target_variant },
+        ),
+        VariantMapping::TupleToTuple {
+            source_variant,
+            fields_mapping,
+        } => {
+            let source_fields = (0..variant.fields.len())
+                .map(|field_source_pos| quote::format_ident!("field_{field_source_pos}"))
+                .collect::<Vec<_>>();
+            let target_fields = source_fields
+                .iter()
+                .enumerate()
+                .map(|(field_source_pos, source_field)| {
+                    let field_target_pos = *fields_mapping
+                        .get(&field_source_pos)
+                        .unwrap_or(&field_source_pos);
+                    (
+                        field_target_pos,
+                        convert(source_field, FieldRef::FieldPos(field_source_pos)),
+                    )
+                })
+                .collect::<BTreeMap<_, _>>()
+                .into_values();
+            (
+                quote! { #source_enum::#source_variant(#(#source_fields),*) },
+                quote! { #target_enum::#target_variant(#(#target_fields),*) },
+            )
+        }
+        VariantMapping::TupleToStruct {
+            source_variant,
+            fields_mapping,
+        } => {
+            let (source_fields, target_fields): (Vec<_>, Vec<_>) = (0..variant.fields.len())
+                .map(|field_source_pos| {
+                    let target_ident = fields_mapping
+                        .get(&field_source_pos)
+                        .expect("fields_mapping exhaustiveness should have been checked");
+                    let conversion = convert(target_ident, FieldRef::FieldPos(field_source_pos));
+                    (
+                        quote! { #target_ident },
+                        quote! { #target_ident: #conversion },
+                    )
+                })
+                .unzip();
+            (
+                quote! { #source_enum::#source_variant(#(#source_fields),*) },
+                quote! { #target_enum::#target_variant { #(#target_fields),* } },
+            )
+        }
+        VariantMapping::StructToStruct {
+            source_variant,
+            fields_mapping,
+        } => {
+            let (source_fields, target_fields): (Vec<_>, Vec<_>) = variant
+                .fields
+                .iter()
+                .map(|field| {
+                    let source_field = FieldIdent(
+                        field
+                            .ident
+                            .as_ref()
+                            .expect("A named field should always have an ident")
+                            .clone(),
+                    );
+                    let target_field = &fields_mapping.get(&source_field).unwrap_or(&source_field);
+                    let conversion =
+                        convert(&source_field, FieldRef::FieldIdent(source_field.clone()));
+                    (
+                        quote! { #source_field },
+                        quote! { #target_field: #conversion },
+                    )
+                })
+                .unzip();
+
+            (
+                quote! { #source_enum::#source_variant { #(#source_fields),* } },
+                quote! { #target_enum::#target_variant { #(#target_fields),* } },
+            )
+        }
+        VariantMapping::StructToTuple {
+            source_variant,
+            fields_mapping,
+        } => {
+            let (source_fields, target_fields): (Vec<_>, Vec<_>) = fields_mapping
+                .into_iter()
+                .map(|(source_ident, target_pos)| (target_pos, source_ident))
+                .collect::<BTreeMap<usize, FieldIdent>>()
+                .into_values()
+                .map(|source_ident| {
+                    let conversion =
+                        convert(&source_ident, FieldRef::FieldIdent(source_ident.clone()));
+                    (quote! { #source_ident }, conversion)
+                })
+                .unzip();
+
+            (
+                quote! { #source_enum::#source_variant { #(#source_fields),* } },
+                quote! { #target_enum::#target_variant(#(#target_fields),*) },
+            )
+        }
+    }
+}
@@ -0,0 +1,79 @@
+//! Snapshot tests of the code generated by the derive macros.
+//!
+//! Each file of `tests/expand` is expanded into the `.expanded.rs` file of the same name, which
+//! is overwritten when `SNAPSHOTS=overwrite` is set.
+use std::{fs, path::Path};
+
+use proc_macro2::TokenStream;
+use quote::ToTokens;
+use syn::{Item, ItemEnum, Meta, Path as SynPath, Token, punctuated::Punctuated};
+
+use crate::{enum_from, enum_into};
+
+fn expand_derives(item: &ItemEnum) -> Vec<TokenStream> {
+    item.attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("derive"))
+        .filter_map(|attr| match &attr.meta {
+            Meta::List(list) => list
+                .parse_args_with(Punctuated::<SynPath, Token![,]>::parse_terminated)
+                .ok(),
+            Meta::Path(_) | Meta::NameValue(_) => None,
+        })
+        .flatten()
+        .filter_map(|derive| {
+            let derive = derive.segments.last()?.ident.to_string();
+            let input = item.to_token_stream();
+            match derive.as_str() {
+                "EnumFrom" => Some(enum_from::derive_enum_from_impl(input)),
+                "EnumFromAsync" => Some(enum_from::derive_enum_from_async_impl(input)),
+                "EnumInto" => Some(enum_into::derive_enum_into_impl(input)),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+fn expand(path: &Path) -> String {
+    let file = syn::parse_file(&fs::read_to_string(path).unwrap()).unwrap();
+    let expanded = file
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Enum(item) => Some(expand_derives(item)),
+            _ => None,
+        })
+        .flatten()
+        .collect::<TokenStream>();
+    prettyplease::unparse(&syn::parse2(expanded).unwrap())
+}
+
+#[test]
+fn expansion_snapshots() {
+    let overwrite = std::env::var("SNAPSHOTS").is_ok_and(|value| value == "overwrite");
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/expand");
+    let mut paths = fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| {
+            path.extension().is_some_and(|extension| extension == "rs")
+                && !path.to_string_lossy().ends_with(".expanded.rs")
+        })
+        .collect::<Vec<_>>();
+    paths.sort();
+    assert!(!paths.is_empty());
+    let mut mismatches = Vec::new();
+    for path in paths {
+        let expanded = expand(&path);
+        let snapshot = path.with_extension("expanded.rs");
+        if overwrite {
+            fs::write(&snapshot, expanded).unwrap();
+        } else if fs::read_to_string(&snapshot).ok().as_deref() != Some(expanded.as_str()) {
+            mismatches.push(snapshot.display().to_string());
+        }
+    }
+    assert!(
+        mismatches.is_empty(),
+        "the expansions differ from {mismatches:?}, run with SNAPSHOTS=overwrite to update them",
+    );
+}
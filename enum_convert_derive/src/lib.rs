@@ -0,0 +1,393 @@
+//! Derive macros of the [enum_convert](https://docs.rs/enum_convert) crate.
+//!
+//! This crate is not meant to be used directly, use `enum_convert` instead.
+use proc_macro::TokenStream;
+
+mod debug;
+mod dump;
+mod enum_from;
+mod enum_into;
+#[cfg(test)]
+mod expansion_tests;
+mod mapping;
+mod registry;
+mod subset;
+mod types;
+
+/// Derives `From<Source> for AnnotatedEnum`.
+///
+/// # Examples
+///
+/// ## Single source enum
+/// ```
+/// use enum_convert::EnumFrom;
+///
+/// enum Source {
+///     Unit,
+///     OtherUnit,
+///     Tuple(i32, &'static str),
+///     DifferentName { x: i32, y: i32 },
+/// }
+///
+/// #[derive(EnumFrom)]
+/// #[enum_from(Source)]
+/// enum Target {
+///     #[enum_from(Source::Unit, Source::OtherUnit)]
+///     Unit,
+///     #[enum_from] // If there is only one mapping and it has the same name, there is no need to specify the variant
+///     Tuple(i64, String),
+///     #[enum_from(Source::DifferentName)]
+///     Struct { x: f64, y: f64 },
+///     Extra, // This variant cannot be built from Source
+/// }
+///
+/// let source = Source::Unit;
+/// let target: Target = source.into();
+/// assert!(matches!(target, Target::Unit));
+///
+/// // Target::Unit can also come from Source::OtherUnit
+/// let source = Source::OtherUnit;
+/// let target: Target = source.into();
+/// assert!(matches!(target, Target::Unit));
+///
+/// let source = Source::Tuple(42, "hello");
+/// let target: Target = source.into();
+/// assert!(matches!(target, Target::Tuple(42, ref s) if s == "hello"));
+///
+/// let source = Source::DifferentName { x: 1, y: 2 };
+/// let target: Target = source.into();
+/// assert!(matches!(target, Target::Struct { x, y } if x == 1.0 && y == 2.0));
+/// ```
+///
+/// ## Multiple source enums with fields mapping
+/// ```
+/// use enum_convert::EnumFrom;
+///
+/// enum FirstSource {
+///     Unit,
+///     Tuple(&'static str, i32),
+///     DifferentName {
+///         alpha: f64,
+///         y: f64,
+///         s: &'static str,
+///     },
+/// }
+///
+/// enum SecondSource {
+///     Empty,
+///     Data(i32, i32, &'static str),
+/// }
+///
+/// #[derive(EnumFrom)]
+/// #[enum_from(FirstSource, SecondSource)]
+/// enum Target {
+///     #[enum_from(FirstSource, SecondSource::Empty)]
+///     Unit,
+///     #[enum_from(FirstSource)]
+///     Tuple(
+///         #[enum_from(FirstSource::Tuple.1)] i64,
+///         #[enum_from(FirstSource::Tuple.0)] String,
+///     ),
+///     #[enum_from(FirstSource::DifferentName, SecondSource)]
+///     Data {
+///         #[enum_from(FirstSource::DifferentName.alpha, SecondSource::Data.0)]
+///         x: f64,
+///         #[enum_from(SecondSource::Data.1)]
+///         y: f64,
+///         #[enum_from(SecondSource::Data.2)]
+///         s: &'static str,
+///     },
+///     Extra,
+/// }
+///
+/// let first_source = FirstSource::Unit;
+/// let target: Target = first_source.into();
+/// assert!(matches!(target, Target::Unit));
+///
+/// // Target::Unit can also come from SecondSource::Empty
+/// let second_source = SecondSource::Empty;
+/// let target: Target = second_source.into();
+/// assert!(matches!(target, Target::Unit));
+///
+/// let first_source = FirstSource::Tuple("hello", 42);
+/// let target: Target = first_source.into();
+/// assert!(matches!(target, Target::Tuple(42, ref s) if s == "hello"));
+///
+/// let first_source = FirstSource::DifferentName { alpha: 1.0, y: 2.0, s: "hello" };
+/// let target: Target = first_source.into();
+/// assert!(matches!(target, Target::Data { x, y, s } if x == 1.0 && y == 2.0 && s == "hello"));
+///
+/// // Target::Data can also come from SecondSource::Data
+/// let second_source = SecondSource::Data(1, 2, "hello");
+/// let target: Target = second_source.into();
+/// assert!(matches!(target, Target::Data { x, y, s } if x == 1.0 && y == 2.0 && s == "hello"));
+/// ```
+#[proc_macro_derive(EnumFrom, attributes(enum_from))]
+pub fn derive_enum_from(input: TokenStream) -> TokenStream {
+    enum_from::derive_enum_from_impl(input.into()).into()
+}
+
+/// Derives `FromAsync<Source> for AnnotatedEnum`.
+///
+/// It accepts the same annotations as [`EnumFrom`] plus:
+/// - `error = ErrorType` in the enum annotation to set the error type of the conversion,
+///   [`Infallible`](core::convert::Infallible) by default.
+/// - `with = path::to::function` in a field annotation to convert that field with an async
+///   function returning a `Result` of the field type, whose error can be converted into the
+///   error type.
+///
+/// # Examples
+///
+/// ```
+/// use enum_convert::{EnumFromAsync, FromAsync};
+///
+/// #[derive(Debug)]
+/// struct UnknownUser(u32);
+///
+/// async fn resolve_user(id: u32) -> Result<String, UnknownUser> {
+///     match id {
+///         1 => Ok("alice".to_string()),
+///         _ => Err(UnknownUser(id)),
+///     }
+/// }
+///
+/// enum Source {
+///     Login { user_id: u32, attempts: u8 },
+///     Logout,
+/// }
+///
+/// #[derive(EnumFromAsync)]
+/// #[enum_from(Source, error = UnknownUser)]
+/// enum Target {
+///     #[enum_from]
+///     Login {
+///         #[enum_from(Source::Login.user_id, with = resolve_user)]
+///         user: String,
+///         attempts: u32,
+///     },
+///     #[enum_from]
+///     Logout,
+/// }
+///
+/// # fn block_on<F: Future>(future: F) -> F::Output {
+/// #     let mut context = std::task::Context::from_waker(std::task::Waker::noop());
+/// #     match std::pin::pin!(future).poll(&mut context) {
+/// #         std::task::Poll::Ready(output) => output,
+/// #         std::task::Poll::Pending => unreachable!(),
+/// #     }
+/// # }
+/// let source = Source::Login { user_id: 1, attempts: 3 };
+/// let target = block_on(Target::from_async(source));
+/// assert!(matches!(target, Ok(Target::Login { user, attempts: 3 }) if user == "alice"));
+///
+/// let source = Source::Login { user_id: 2, attempts: 3 };
+/// let target = block_on(Target::from_async(source));
+/// assert!(matches!(target, Err(UnknownUser(2))));
+/// ```
+#[proc_macro_derive(EnumFromAsync, attributes(enum_from))]
+pub fn derive_enum_from_async(input: TokenStream) -> TokenStream {
+    enum_from::derive_enum_from_async_impl(input.into()).into()
+}
+
+/// Derives `From<AnnotatedEnum> for Target`.
+///
+/// # Examples
+///
+/// ## Single target enum
+/// ```
+/// use enum_convert::EnumInto;
+///
+/// #[derive(EnumInto)]
+/// #[enum_into(Target)]
+/// enum Source {
+///     Unit,  // Uses same name in target
+///     #[enum_into(Target::Unit)]
+///     OtherUnit,
+///     Tuple(i32, &'static str),  // Uses same name in target
+///     #[enum_into(Target::Struct)]  // Maps to different variant name
+///     DifferentName { x: i32, y: i32 }
+/// }
+///
+/// enum Target {
+///     Unit,
+///     Tuple(i64, String),
+///     Struct { x: f64, y: f64 },
+///     Extra
+/// }
+///
+/// let source = Source::Unit;
+/// let target: Target = source.into();
+/// assert!(matches!(target, Target::Unit));
+///
+/// // Target::Unit can also come from Source::OtherUnit
+/// let source = Source::OtherUnit;
+/// let target: Target = source.into();
+/// assert!(matches!(target, Target::Unit));
+///
+/// let source = Source::Tuple(42, "hello");
+/// let target: Target = source.into();
+/// assert!(matches!(target, Target::Tuple(42, ref s) if s == "hello"));
+///
+/// let source = Source::DifferentName { x: 1, y: 2 };
+/// let target: Target = source.into();
+/// assert!(matches!(target, Target::Struct { x, y } if x == 1.0 && y == 2.0));
+/// ```
+///
+/// ## Multiple target enums with field mapping
+/// ```
+/// use enum_convert::EnumInto;
+///
+/// #[derive(EnumInto)]
+/// #[enum_into(FirstTarget, SecondTarget)]
+/// enum Source {
+///     Unit,  // Goes to both FirstTarget::Unit and SecondTarget::Unit
+///     Tuple(
+///         // Reorder fields for SecondTarget only
+///         #[enum_into(SecondTarget::Tuple.1)] &'static str,
+///         #[enum_into(SecondTarget::Tuple.0)] i32,
+///     ),
+///     #[enum_into(FirstTarget::Data, SecondTarget::Info)]  // Maps to different variants
+///     Record {
+///         #[enum_into(FirstTarget::Data.name, SecondTarget::Info.0)]  // Maps fields differently
+///         label: String,
+///         #[enum_into(SecondTarget::Info.1)]
+///         value: i32
+///     },
+/// }
+///
+/// enum FirstTarget {
+///     Unit,
+///     Tuple(String, i32),
+///     Data { name: String, value: i64 },
+/// }
+///
+/// enum SecondTarget {
+///     Unit,
+///     Tuple(i32, String),
+///     Info(String, i64),
+/// }
+///
+/// let source = Source::Unit;
+/// let first_target: FirstTarget = source.into();
+/// assert!(matches!(first_target, FirstTarget::Unit));
+///
+/// // Source::Unit can also go to SecondTarget::Unit
+/// let source = Source::Unit;
+/// let second_target: SecondTarget = source.into();
+/// assert!(matches!(second_target, SecondTarget::Unit));
+///
+/// let source = Source::Tuple("hello", 42);
+/// let first_target: FirstTarget = source.into();
+/// assert!(matches!(first_target, FirstTarget::Tuple(ref s, value) if s == "hello" && value == 42));
+///
+/// // Source::Tuple can also go to SecondTarget::Into with different fields order
+/// let source = Source::Tuple("hello", 42);
+/// let second_target: SecondTarget = source.into();
+/// assert!(matches!(second_target, SecondTarget::Tuple(value, ref s) if s == "hello" && value == 42));
+///
+/// let source = Source::Record { label: "test".to_string(), value: 42 };
+/// let first_target: FirstTarget = source.into();
+/// assert!(matches!(first_target, FirstTarget::Data { name, value } if name == "test" && value == 42));
+///
+/// // Source::Record can also go to SecondTarget::Info with different field mapping
+/// let source = Source::Record { label: "test".to_string(), value: 42 };
+/// let second_target: SecondTarget = source.into();
+/// assert!(matches!(second_target, SecondTarget::Info(label, value) if label == "test" && value == 42));
+/// ```
+#[proc_macro_derive(EnumInto, attributes(enum_into))]
+pub fn derive_enum_into(input: TokenStream) -> TokenStream {
+    enum_into::derive_enum_into_impl(input.into()).into()
+}
+
+/// Registers the shape of an enum, so that the derive macros can check their annotations against
+/// its definition.
+///
+/// The enum is then used with the `registered` option of the annotations of [`EnumFrom`],
+/// [`EnumFromAsync`] and [`EnumInto`], which report the unknown or unmapped variants and fields
+/// of the registered enum instead of leaving them to the compiler errors in the generated code.
+/// The registered enum must be defined in the same crate, before the annotated enum, and be in
+/// scope where the annotated enum is defined.
+///
+/// With `#[enum_convert::register(export)]`, the shape is also exported at the root of the crate,
+/// so that [`enum_subset!`](macro@enum_subset) can be used from other crates.
+///
+/// # Example
+/// ```
+/// use enum_convert::EnumFrom;
+///
+/// #[enum_convert::register]
+/// enum Source {
+///     Unit,
+///     Struct { x: i32, y: i32 },
+/// }
+///
+/// #[derive(EnumFrom)]
+/// #[enum_from(Source, registered)]
+/// enum Target {
+///     #[enum_from]
+///     Unit,
+///     #[enum_from]
+///     Struct { x: i64, y: i64 },
+/// }
+/// # fn main() {}
+/// ```
+#[proc_macro_attribute]
+pub fn register(attr: TokenStream, item: TokenStream) -> TokenStream {
+    registry::register_impl(attr, item)
+}
+
+/// The callback of the shape macros generated by [`register`](macro@register).
+#[doc(hidden)]
+#[proc_macro]
+pub fn __check_registered(input: TokenStream) -> TokenStream {
+    registry::check_registered_impl(input)
+}
+
+/// Defines an enum with a subset of the variants of a [registered](macro@register) enum, along
+/// with `From<Subset> for Source` and `TryFrom<Source> for Subset`.
+///
+/// The selected variants can be renamed with `Renamed = SourceVariant` and get the same fields as
+/// in the source enum, whose types must be in scope where the subset is defined.
+/// The conversion from the source enum fails with the original value when its variant is not part
+/// of the subset.
+///
+/// A path starting with `::` refers to an enum of another crate, which must be registered with
+/// `#[enum_convert::register(export)]`, otherwise the registered enum must be in scope.
+///
+/// # Example
+/// ```
+/// mod internal {
+///     #[enum_convert::register]
+///     #[derive(Debug)]
+///     pub enum Event {
+///         Created { id: u32 },
+///         Deleted(u32),
+///         Migrated,
+///     }
+/// }
+///
+/// enum_convert::enum_subset! {
+///     #[derive(Debug)]
+///     pub enum PublicEvent from internal::Event { Created, Removed = Deleted }
+/// }
+///
+/// let event: internal::Event = PublicEvent::Removed(1).into();
+/// assert!(matches!(event, internal::Event::Deleted(1)));
+///
+/// let event = PublicEvent::try_from(internal::Event::Created { id: 2 });
+/// assert!(matches!(event, Ok(PublicEvent::Created { id: 2 })));
+///
+/// let event = PublicEvent::try_from(internal::Event::Migrated);
+/// assert!(matches!(event, Err(internal::Event::Migrated)));
+/// ```
+#[proc_macro]
+pub fn enum_subset(input: TokenStream) -> TokenStream {
+    subset::enum_subset_impl(input)
+}
+
+/// The callback of the shape macros invoked by [`enum_subset!`](macro@enum_subset).
+#[doc(hidden)]
+#[proc_macro]
+pub fn __enum_subset(input: TokenStream) -> TokenStream {
+    subset::generate_subset_impl(input)
+}
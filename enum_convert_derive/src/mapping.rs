@@ -0,0 +1,110 @@
+//! The `EnumMapping` and `EnumMappingAsync` implementations, describing the derived conversions
+//! at runtime.
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use syn::LitStr;
+
+use enum_convert_core::idents::ContainerIdent;
+
+use crate::dump::dump_mapping;
+
+/// The conversion of a source variant into a target variant.
+pub struct MappedVariant {
+    pub source: String,
+    /// An expression of the name of the target variant, which may be computed at compile time.
+    pub target: TokenStream,
+    pub fields: Vec<MappedField>,
+}
+
+/// The conversion of a source field into a target field, by name or position.
+pub struct MappedField {
+    pub source: String,
+    pub target: String,
+}
+
+impl MappedVariant {
+    pub fn new(source: impl ToString, target: impl ToString, fields: Vec<MappedField>) -> Self {
+        let target = target.to_string();
+        Self {
+            source: source.to_string(),
+            target: quote! { #target },
+            fields,
+        }
+    }
+
+    /// A conversion whose target variant is not known while expanding the macros, which can only
+    /// be dumped.
+    pub fn unknown_target(source: impl ToString) -> Self {
+        Self {
+            source: source.to_string(),
+            target: TokenStream::new(),
+            fields: Vec::new(),
+        }
+    }
+
+    /// The name of the target variant, unless it is computed at compile time.
+    pub fn target_name(&self) -> Option<String> {
+        syn::parse2::<LitStr>(self.target.clone())
+            .ok()
+            .map(|target| target.value())
+    }
+}
+
+impl MappedField {
+    pub fn new(source: impl ToString, target: impl ToString) -> Self {
+        Self {
+            source: source.to_string(),
+            target: target.to_string(),
+        }
+    }
+}
+
+/// Sorts the conversions by source variant.
+pub fn sort_variants(variants: &mut [MappedVariant]) {
+    // The sort is stable, so the conversions of a source variant stay in the order they are
+    // matched.
+    variants.sort_by(|a, b| a.source.cmp(&b.source));
+}
+
+/// Generates the implementation of `mapping_trait`, if any, and dumps the conversion unless it is
+/// dumped once checked against the registered enum.
+pub fn generate_mapping_impl(
+    source_enum: &ContainerIdent,
+    target_enum: &ContainerIdent,
+    mut variants: Vec<MappedVariant>,
+    mapping_trait: Option<TokenStream>,
+    dump: bool,
+) -> TokenStream {
+    sort_variants(&mut variants);
+    let dump = dump.then(|| dump_mapping(source_enum, target_enum, &variants));
+    let Some(mapping_trait) = mapping_trait else {
+        return dump.unwrap_or_default();
+    };
+    let variants = variants.iter().map(
+        |MappedVariant {
+             source,
+             target,
+             fields,
+         }| {
+            let fields = fields.iter().map(|MappedField { source, target }| {
+                quote! { ::enum_convert::FieldMapping { source: #source, target: #target } }
+            });
+            quote! {
+                ::enum_convert::VariantMapping {
+                    source: #source,
+                    target: #target,
+                    fields: &[#(#fields),*],
+                }
+            }
+        },
+    );
+    quote! {
+        #[automatically_derived]
+        impl #mapping_trait<#target_enum> for #source_enum {
+            const VARIANTS: &'static [::enum_convert::VariantMapping] = &[#(#variants),*];
+        }
+
+        #dump
+    }
+}
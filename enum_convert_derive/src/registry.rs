@@ -0,0 +1,507 @@
+//! The registry of the enum shapes, letting the derive macros check the annotations against the
+//! definition of the other enum.
+//!
+//! `#[enum_convert::register]` defines a hidden `macro_rules!` next to the enum, imported along
+//! with the enum since it is re-exported under the same name in the macro namespace.
+//! With `#[enum_convert::register(export)]`, it is also exported at the root of the crate under
+//! the name returned by `exported_shape_macro`, since the exported macros defined by a macro
+//! cannot be re-exported.
+//! With the `registered` option, the derive macros invoke it with a `ShapeCheck` and the
+//! callback `__check_registered` receives both, so that it can check the expectations of the
+//! derive macro against the definition of the enum before emitting the generated code.
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{ToTokens, format_ident, quote};
+use syn::{
+    BinOp, Expr, ExprBinary, ExprLit, Fields, Ident, ItemEnum, Lit, LitBool, LitInt, Token, UnOp,
+    braced, bracketed, parenthesized,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    token,
+};
+
+use enum_convert_core::{
+    discriminants::discriminants, errors::Errors, idents::ContainerIdent, suggestions::did_you_mean,
+};
+
+use crate::{
+    dump::dump_mapping,
+    mapping::{MappedVariant, sort_variants},
+};
+
+pub fn register_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let export = match syn::parse::<Option<Ident>>(attr.clone()) {
+        Ok(None) => false,
+        Ok(Some(ident)) if ident == "export" => true,
+        _ => {
+            return syn::Error::new_spanned(
+                TokenStream2::from(attr),
+                "unknown argument, expected `export`",
+            )
+            .into_compile_error()
+            .into();
+        }
+    };
+    let item_enum = match syn::parse::<ItemEnum>(item) {
+        Ok(item_enum) => item_enum,
+        Err(_) => {
+            return syn::Error::new(Span::call_site(), "only enums can be registered")
+                .into_compile_error()
+                .into();
+        }
+    };
+    let ident = &item_enum.ident;
+    let macro_ident = format_ident!("__enum_convert_local_shape_{}", ident);
+    let shape = generate_shape(&item_enum);
+    let exported_macro = export.then(|| {
+        let exported_macro_ident = exported_shape_macro(ident);
+        quote! {
+            #[doc(hidden)]
+            #[macro_export]
+            macro_rules! #exported_macro_ident {
+                ([$($callback:tt)*] $($check:tt)*) => {
+                    $($callback)*! { $($check)* #shape }
+                };
+            }
+        }
+    });
+
+    quote! {
+        #item_enum
+
+        #[doc(hidden)]
+        macro_rules! #macro_ident {
+            ([$($callback:tt)*] $($check:tt)*) => {
+                $($callback)*! { $($check)* #shape }
+            };
+        }
+
+        #[doc(hidden)]
+        #[allow(unused_imports)]
+        pub(crate) use #macro_ident as #ident;
+
+        #exported_macro
+    }
+    .into()
+}
+
+/// The name of the shape macro exported at the root of the crate defining the enum.
+pub fn exported_shape_macro(ident: &Ident) -> Ident {
+    format_ident!("__enum_convert_shape_{}", ident)
+}
+
+/// The definition of the enum without its attributes, which only matter to the compiler.
+fn generate_shape(item_enum: &ItemEnum) -> TokenStream2 {
+    let ident = &item_enum.ident;
+    let variants = item_enum.variants.iter().map(|variant| {
+        let variant_ident = &variant.ident;
+        let fields = variant.fields.iter().map(|field| {
+            let ty = &field.ty;
+            match &field.ident {
+                Some(field_ident) => quote! { #field_ident: #ty },
+                None => quote! { #ty },
+            }
+        });
+        let discriminant = variant
+            .discriminant
+            .as_ref()
+            .map(|(_, discriminant)| quote! { = #discriminant });
+        match &variant.fields {
+            Fields::Unit => quote! { #variant_ident #discriminant },
+            Fields::Unnamed(_) => quote! { #variant_ident(#(#fields),*) #discriminant },
+            Fields::Named(_) => quote! { #variant_ident { #(#fields),* } #discriminant },
+        }
+    });
+    quote! { enum #ident { #(#variants),* } }
+}
+
+pub fn check_registered_impl(input: TokenStream) -> TokenStream {
+    syn::parse::<RegisteredInput>(input)
+        .and_then(|RegisteredInput { check, shape }| {
+            check.check(&shape)?;
+            let dump = check.discriminants.as_ref().map(|discriminants| {
+                dump_mapping(
+                    &ContainerIdent(check.registered_enum.clone()),
+                    &ContainerIdent(check.annotated_enum.clone()),
+                    &check.discriminant_mapped_variants(&shape, discriminants),
+                )
+            });
+            let output = check.output;
+            Ok(quote! {
+                #output
+                #dump
+            })
+        })
+        .unwrap_or_else(|err| err.into_compile_error())
+        .into()
+}
+
+struct RegisteredInput {
+    check: ShapeCheck,
+    shape: ItemEnum,
+}
+
+impl Parse for RegisteredInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            check: input.parse()?,
+            shape: input.parse()?,
+        })
+    }
+}
+
+/// What a derive macro expects of the registered enum, with the code it generated assuming these
+/// expectations are met.
+pub struct ShapeCheck {
+    pub registered_enum: Ident,
+    pub annotated_enum: Ident,
+    /// Whether all the variants of the registered enum must be expected, which is the case when
+    /// converting from it.
+    pub exhaustive: bool,
+    pub variants: Vec<ExpectedVariant>,
+    /// The discriminants of the variants of the annotated enum when the variants of the
+    /// registered enum are converted by discriminant, so that the conversion is dumped with the
+    /// target variants once the discriminants of the registered enum are known.
+    pub discriminants: Option<Vec<(Ident, TokenStream2)>>,
+    pub output: TokenStream2,
+}
+
+pub struct ExpectedVariant {
+    pub ident: Ident,
+    pub fields: ExpectedFields,
+}
+
+/// The fields of a variant which are matched or built by the generated code.
+pub enum ExpectedFields {
+    /// The fields are matched by a pattern, which is checked by the compiler.
+    Unknown,
+    Unit,
+    Tuple(Vec<usize>),
+    Struct(Vec<Ident>),
+}
+
+impl ShapeCheck {
+    /// Generates the invocation of the shape macro of the registered enum.
+    pub fn generate(self) -> TokenStream2 {
+        let registered_enum = &self.registered_enum;
+        quote! {
+            #registered_enum! { [::enum_convert::__check_registered] #self }
+        }
+    }
+
+    fn check(&self, shape: &ItemEnum) -> syn::Result<()> {
+        let mut errors = Errors::default();
+        let registered_enum = &self.registered_enum;
+        for ExpectedVariant { ident, fields } in &self.variants {
+            let Some(variant) = shape
+                .variants
+                .iter()
+                .find(|variant| variant.ident == *ident)
+            else {
+                errors.push(syn::Error::new(
+                    ident.span(),
+                    did_you_mean(
+                        format!("`{registered_enum}` has no variant `{ident}`"),
+                        ident,
+                        shape.variants.iter().map(|variant| &variant.ident),
+                    ),
+                ));
+                continue;
+            };
+            errors.ok(fields.check(registered_enum, ident, &variant.fields));
+        }
+        if self.exhaustive {
+            for variant in &shape.variants {
+                if !self
+                    .variants
+                    .iter()
+                    .any(|expected| expected.ident == variant.ident)
+                {
+                    errors.push(syn::Error::new(
+                        registered_enum.span(),
+                        format!(
+                            "`{registered_enum}::{}` is not converted into any variant of `{}`",
+                            variant.ident, self.annotated_enum
+                        ),
+                    ));
+                }
+            }
+        }
+        errors.finish()
+    }
+
+    /// The conversions of the expected variants into the first variants with the same
+    /// discriminant, as matched by the generated code.
+    /// The target of a variant is unknown when a discriminant before it is not made of integer
+    /// literals.
+    fn discriminant_mapped_variants(
+        &self,
+        shape: &ItemEnum,
+        target_discriminants: &[(Ident, TokenStream2)],
+    ) -> Vec<MappedVariant> {
+        let source_discriminants = shape
+            .variants
+            .iter()
+            .zip(discriminants(&shape.variants))
+            .map(|(variant, discriminant)| (&variant.ident, evaluate(discriminant)))
+            .collect::<Vec<_>>();
+        let mut variants = self
+            .variants
+            .iter()
+            .map(|ExpectedVariant { ident, .. }| {
+                let discriminant = source_discriminants
+                    .iter()
+                    .find(|(variant, _)| *variant == ident)
+                    .and_then(|(_, discriminant)| *discriminant);
+                let target = discriminant.and_then(|discriminant| {
+                    target_discriminants
+                        .iter()
+                        .map(|(target, target_discriminant)| {
+                            (target, evaluate(target_discriminant.clone()))
+                        })
+                        .find(|(_, target_discriminant)| {
+                            target_discriminant.is_none_or(|value| value == discriminant)
+                        })
+                        .and_then(|(target, target_discriminant)| {
+                            target_discriminant.map(|_| target)
+                        })
+                });
+                match target {
+                    Some(target) => MappedVariant::new(ident, target, Vec::new()),
+                    None => MappedVariant::unknown_target(ident),
+                }
+            })
+            .collect::<Vec<_>>();
+        sort_variants(&mut variants);
+        variants
+    }
+}
+
+/// The value of a discriminant made of integer literals, as computed by `discriminants`.
+fn evaluate(discriminant: TokenStream2) -> Option<i128> {
+    fn evaluate_expr(expr: &Expr) -> Option<i128> {
+        match expr {
+            Expr::Lit(ExprLit {
+                lit: Lit::Int(lit), ..
+            }) => lit.base10_parse().ok(),
+            Expr::Paren(paren) => evaluate_expr(&paren.expr),
+            Expr::Group(group) => evaluate_expr(&group.expr),
+            Expr::Unary(unary) if matches!(unary.op, UnOp::Neg(_)) => {
+                evaluate_expr(&unary.expr)?.checked_neg()
+            }
+            Expr::Binary(ExprBinary {
+                left,
+                op: BinOp::Add(_),
+                right,
+                ..
+            }) => evaluate_expr(left)?.checked_add(evaluate_expr(right)?),
+            _ => None,
+        }
+    }
+    evaluate_expr(&syn::parse2(discriminant).ok()?)
+}
+
+impl ExpectedFields {
+    fn check(&self, registered_enum: &Ident, variant: &Ident, fields: &Fields) -> syn::Result<()> {
+        let mut errors = Errors::default();
+        match (self, fields) {
+            (Self::Unknown, _) | (Self::Unit, Fields::Unit) => {}
+            (Self::Tuple(positions), Fields::Unnamed(fields)) => {
+                let len = fields.unnamed.len();
+                for position in positions.iter().filter(|position| **position >= len) {
+                    errors.push(syn::Error::new(
+                        variant.span(),
+                        format!("`{registered_enum}::{variant}` has no field `{position}`"),
+                    ));
+                }
+                for position in (0..len).filter(|position| !positions.contains(position)) {
+                    errors.push(syn::Error::new(
+                        variant.span(),
+                        format!(
+                            "the field `{position}` of `{registered_enum}::{variant}` is not mapped"
+                        ),
+                    ));
+                }
+            }
+            (Self::Struct(idents), Fields::Named(fields)) => {
+                let names = fields
+                    .named
+                    .iter()
+                    .filter_map(|field| field.ident.as_ref())
+                    .collect::<Vec<_>>();
+                for ident in idents.iter().filter(|ident| !names.contains(ident)) {
+                    errors.push(syn::Error::new(
+                        ident.span(),
+                        did_you_mean(
+                            format!("`{registered_enum}::{variant}` has no field `{ident}`"),
+                            ident,
+                            &names,
+                        ),
+                    ));
+                }
+                for name in names.iter().filter(|name| !idents.contains(name)) {
+                    errors.push(syn::Error::new(
+                        variant.span(),
+                        format!(
+                            "the field `{name}` of `{registered_enum}::{variant}` is not mapped"
+                        ),
+                    ));
+                }
+            }
+            (_, _) => errors.push(syn::Error::new(
+                variant.span(),
+                format!(
+                    "`{registered_enum}::{variant}` is {}, not {}",
+                    fields_kind(fields),
+                    self.kind()
+                ),
+            )),
+        }
+        errors.finish()
+    }
+
+    fn kind(&self) -> &'static str {
+        match self {
+            Self::Unknown => "any variant",
+            Self::Unit => "a unit variant",
+            Self::Tuple(_) => "a tuple variant",
+            Self::Struct(_) => "a struct variant",
+        }
+    }
+}
+
+fn fields_kind(fields: &Fields) -> &'static str {
+    match fields {
+        Fields::Unit => "a unit variant",
+        Fields::Unnamed(_) => "a tuple variant",
+        Fields::Named(_) => "a struct variant",
+    }
+}
+
+impl ToTokens for ShapeCheck {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        let Self {
+            registered_enum,
+            annotated_enum,
+            exhaustive,
+            variants,
+            discriminants,
+            output,
+        } = self;
+        let exhaustive = LitBool::new(*exhaustive, Span::call_site());
+        let discriminants = match discriminants {
+            Some(discriminants) => {
+                let discriminants = discriminants
+                    .iter()
+                    .map(|(variant, discriminant)| quote! { #variant = (#discriminant) });
+                quote! { [#(#discriminants),*] }
+            }
+            None => quote! { _ },
+        };
+        tokens.extend(quote! {
+            #registered_enum #annotated_enum #exhaustive [#(#variants),*] #discriminants
+            { #output }
+        });
+    }
+}
+
+impl Parse for ShapeCheck {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let registered_enum = input.parse()?;
+        let annotated_enum = input.parse()?;
+        let exhaustive = input.parse::<LitBool>()?.value;
+        let variants;
+        bracketed!(variants in input);
+        let variants = Punctuated::<ExpectedVariant, Token![,]>::parse_terminated(&variants)?;
+        let discriminants = if input.peek(Token![_]) {
+            input.parse::<Token![_]>()?;
+            None
+        } else {
+            let discriminants;
+            bracketed!(discriminants in input);
+            let discriminants =
+                Punctuated::<DiscriminantItem, Token![,]>::parse_terminated(&discriminants)?;
+            Some(
+                discriminants
+                    .into_iter()
+                    .map(|DiscriminantItem(variant, discriminant)| (variant, discriminant))
+                    .collect(),
+            )
+        };
+        let output;
+        braced!(output in input);
+        Ok(Self {
+            registered_enum,
+            annotated_enum,
+            exhaustive,
+            variants: variants.into_iter().collect(),
+            discriminants,
+            output: output.parse()?,
+        })
+    }
+}
+
+/// A variant of the annotated enum with its discriminant, for example `Unit = (1)`.
+struct DiscriminantItem(Ident, TokenStream2);
+
+impl Parse for DiscriminantItem {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let variant = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let discriminant;
+        parenthesized!(discriminant in input);
+        Ok(Self(variant, discriminant.parse()?))
+    }
+}
+
+impl ToTokens for ExpectedVariant {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        let ident = &self.ident;
+        let fields = match &self.fields {
+            ExpectedFields::Unknown => quote! { _ },
+            ExpectedFields::Unit => quote! { unit },
+            ExpectedFields::Tuple(positions) => {
+                let positions = positions
+                    .iter()
+                    .map(|position| LitInt::new(&position.to_string(), ident.span()));
+                quote! { (#(#positions),*) }
+            }
+            ExpectedFields::Struct(idents) => quote! { { #(#idents),* } },
+        };
+        tokens.extend(quote! { #ident = #fields });
+    }
+}
+
+impl Parse for ExpectedVariant {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ident = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let fields = if input.peek(Token![_]) {
+            input.parse::<Token![_]>()?;
+            ExpectedFields::Unknown
+        } else if input.peek(token::Paren) {
+            let positions;
+            parenthesized!(positions in input);
+            let positions = Punctuated::<LitInt, Token![,]>::parse_terminated(&positions)?
+                .iter()
+                .map(LitInt::base10_parse)
+                .collect::<syn::Result<_>>()?;
+            ExpectedFields::Tuple(positions)
+        } else if input.peek(token::Brace) {
+            let idents;
+            braced!(idents in input);
+            let idents = Punctuated::<Ident, Token![,]>::parse_terminated(&idents)?;
+            ExpectedFields::Struct(idents.into_iter().collect())
+        } else {
+            let unit: Ident = input.parse()?;
+            if unit != "unit" {
+                Err(syn::Error::new(
+                    unit.span(),
+                    "expected the fields of the variant",
+                ))?
+            }
+            ExpectedFields::Unit
+        };
+        Ok(Self { ident, fields })
+    }
+}
@@ -0,0 +1,299 @@
+//! `enum_subset!`, defining an enum with a subset of the variants of a registered enum.
+//!
+//! The macro invokes the shape macro of the registered enum with the callback `__enum_subset`,
+//! which receives the definition of the subset followed by the shape, so that it can copy the
+//! fields of the selected variants and generate the conversions.
+use std::collections::HashSet;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{ToTokens, format_ident, quote};
+use syn::{
+    Attribute, Fields, Ident, ItemEnum, Path, Token, Visibility, braced,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+};
+
+use enum_convert_core::{errors::Errors, suggestions::did_you_mean};
+
+use crate::registry::exported_shape_macro;
+
+pub fn enum_subset_impl(input: TokenStream) -> TokenStream {
+    syn::parse::<SubsetDefinition>(input)
+        .and_then(|definition| {
+            definition.check()?;
+            let shape_macro = definition.shape_macro();
+            Ok(quote! {
+                #shape_macro! { [::enum_convert::__enum_subset] #definition }
+            })
+        })
+        .unwrap_or_else(|err| err.into_compile_error())
+        .into()
+}
+
+pub fn generate_subset_impl(input: TokenStream) -> TokenStream {
+    syn::parse::<SubsetInput>(input)
+        .and_then(|SubsetInput { definition, shape }| definition.generate(&shape))
+        .unwrap_or_else(|err| err.into_compile_error())
+        .into()
+}
+
+struct SubsetInput {
+    definition: SubsetDefinition,
+    shape: ItemEnum,
+}
+
+impl Parse for SubsetInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            definition: input.parse()?,
+            shape: input.parse()?,
+        })
+    }
+}
+
+/// `#[attrs] pub enum Subset from path::to::Source { A, Renamed = B }`
+struct SubsetDefinition {
+    attrs: Vec<Attribute>,
+    vis: Visibility,
+    ident: Ident,
+    source: Path,
+    variants: Vec<SubsetVariant>,
+}
+
+struct SubsetVariant {
+    attrs: Vec<Attribute>,
+    ident: Ident,
+    /// The variant of the source enum, when it has a different name.
+    source_variant: Option<Ident>,
+}
+
+impl SubsetVariant {
+    fn source_variant(&self) -> &Ident {
+        self.source_variant.as_ref().unwrap_or(&self.ident)
+    }
+}
+
+impl SubsetDefinition {
+    fn check(&self) -> syn::Result<()> {
+        let mut errors = Errors::default();
+        let mut idents = HashSet::new();
+        let mut source_variants = HashSet::new();
+        for variant in &self.variants {
+            if !idents.insert(&variant.ident) {
+                errors.push(syn::Error::new(
+                    variant.ident.span(),
+                    format!(
+                        "`{}::{}` is defined multiple times",
+                        self.ident, variant.ident
+                    ),
+                ));
+            }
+            let source_variant = variant.source_variant();
+            if !source_variants.insert(source_variant) {
+                errors.push(syn::Error::new(
+                    source_variant.span(),
+                    format!(
+                        "`{}::{source_variant}` is included multiple times",
+                        self.source_ident()
+                    ),
+                ));
+            }
+        }
+        errors.finish()
+    }
+
+    fn source_ident(&self) -> &Ident {
+        &self
+            .source
+            .segments
+            .last()
+            .expect("a path has at least one segment")
+            .ident
+    }
+
+    /// The shape macro of the source enum: the one exported at the root of its crate when the
+    /// path starts with `::`, otherwise the one imported along with the enum.
+    fn shape_macro(&self) -> TokenStream2 {
+        match (&self.source.leading_colon, self.source.segments.first()) {
+            (Some(_), Some(krate)) if self.source.segments.len() > 1 => {
+                let krate = &krate.ident;
+                let macro_ident = exported_shape_macro(self.source_ident());
+                quote! { ::#krate::#macro_ident }
+            }
+            _ => self.source.to_token_stream(),
+        }
+    }
+
+    fn generate(&self, shape: &ItemEnum) -> syn::Result<TokenStream2> {
+        let mut errors = Errors::default();
+        let source_ident = self.source_ident();
+        let variants = self
+            .variants
+            .iter()
+            .filter_map(|variant| {
+                let source_variant = variant.source_variant();
+                let found = shape
+                    .variants
+                    .iter()
+                    .find(|shape_variant| shape_variant.ident == *source_variant);
+                if found.is_none() {
+                    errors.push(syn::Error::new(
+                        source_variant.span(),
+                        did_you_mean(
+                            format!("`{source_ident}` has no variant `{source_variant}`"),
+                            source_variant,
+                            shape.variants.iter().map(|variant| &variant.ident),
+                        ),
+                    ));
+                }
+                found.map(|shape_variant| (variant, shape_variant))
+            })
+            .collect::<Vec<_>>();
+        errors.finish()?;
+
+        let Self {
+            attrs,
+            vis,
+            ident,
+            source,
+            ..
+        } = self;
+        let definitions = variants.iter().map(|(variant, shape_variant)| {
+            let variant_attrs = &variant.attrs;
+            let variant_ident = &variant.ident;
+            let fields = &shape_variant.fields;
+            quote! { #(#variant_attrs)* #variant_ident #fields }
+        });
+        let into_source_arms = variants.iter().map(|(variant, shape_variant)| {
+            let pattern = fields_pattern(&shape_variant.fields);
+            let variant_ident = &variant.ident;
+            let source_variant = &shape_variant.ident;
+            quote! { #ident::#variant_ident #pattern => #source::#source_variant #pattern }
+        });
+        let from_source_arms = variants.iter().map(|(variant, shape_variant)| {
+            let pattern = fields_pattern(&shape_variant.fields);
+            let variant_ident = &variant.ident;
+            let source_variant = &shape_variant.ident;
+            quote! {
+                #source::#source_variant #pattern => ::core::result::Result::Ok(#ident::#variant_ident #pattern)
+            }
+        });
+
+        Ok(quote! {
+            #(#attrs)*
+            #vis enum #ident {
+                #(#definitions),*
+            }
+
+            #[automatically_derived]
+            impl ::core::convert::From<#ident> for #source {
+                fn from(value: #ident) -> Self {
+                    match value {
+                        #(#into_source_arms,)*
+                    }
+                }
+            }
+
+            #[automatically_derived]
+            impl ::core::convert::TryFrom<#source> for #ident {
+                type Error = #source;
+
+                fn try_from(value: #source) -> ::core::result::Result<Self, Self::Error> {
+                    match value {
+                        #(#from_source_arms,)*
+                        #[allow(unreachable_patterns)]
+                        other => ::core::result::Result::Err(other),
+                    }
+                }
+            }
+        })
+    }
+}
+
+/// The pattern binding the fields of a variant, which also builds the variant with them.
+fn fields_pattern(fields: &Fields) -> TokenStream2 {
+    match fields {
+        Fields::Unit => quote! {},
+        Fields::Unnamed(fields) => {
+            let bindings =
+                (0..fields.unnamed.len()).map(|position| format_ident!("field_{position}"));
+            quote! { (#(#bindings),*) }
+        }
+        Fields::Named(fields) => {
+            let bindings = fields.named.iter().map(|field| &field.ident);
+            quote! { { #(#bindings),* } }
+        }
+    }
+}
+
+impl Parse for SubsetDefinition {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        input.parse::<Token![enum]>()?;
+        let ident = input.parse()?;
+        let from: Ident = input.parse()?;
+        if from != "from" {
+            Err(syn::Error::new(from.span(), "expected `from`"))?
+        }
+        let source = input.call(Path::parse_mod_style)?;
+        let variants;
+        braced!(variants in input);
+        let variants = Punctuated::<SubsetVariant, Token![,]>::parse_terminated(&variants)?;
+        Ok(Self {
+            attrs,
+            vis,
+            ident,
+            source,
+            variants: variants.into_iter().collect(),
+        })
+    }
+}
+
+impl Parse for SubsetVariant {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        let ident = input.parse()?;
+        let source_variant = if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        Ok(Self {
+            attrs,
+            ident,
+            source_variant,
+        })
+    }
+}
+
+impl ToTokens for SubsetDefinition {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        let Self {
+            attrs,
+            vis,
+            ident,
+            source,
+            variants,
+        } = self;
+        tokens.extend(quote! {
+            #(#attrs)* #vis enum #ident from #source { #(#variants),* }
+        });
+    }
+}
+
+impl ToTokens for SubsetVariant {
+    fn to_tokens(&self, tokens: &mut TokenStream2) {
+        let Self {
+            attrs,
+            ident,
+            source_variant,
+        } = self;
+        tokens.extend(quote! { #(#attrs)* #ident });
+        if let Some(source_variant) = source_variant {
+            tokens.extend(quote! { = #source_variant });
+        }
+    }
+}
@@ -0,0 +1,251 @@
+use proc_macro2::TokenStream;
+use quote::{ToTokens, quote};
+use syn::{Fields, GenericArgument, PathArguments, Type};
+
+use enum_convert_core::idents::{ContainerIdent, FieldRef};
+
+/// Returns the type of the given field of a variant.
+pub fn field_type<'a>(fields: &'a Fields, field: &FieldRef) -> Option<&'a Type> {
+    match field {
+        FieldRef::FieldPos(pos) => fields.iter().nth(*pos).map(|field| &field.ty),
+        FieldRef::FieldIdent(ident) => fields
+            .iter()
+            .find(|field| field.ident.as_ref() == Some(&ident.0))
+            .map(|field| &field.ty),
+    }
+}
+
+/// The standard types whose content is converted instead of the value itself.
+enum Container<'a> {
+    /// `Box<Self>` or `Box<AnnotatedEnum>`, converted recursively since there is no
+    /// `From<Box<_>>` implementation to rely on.
+    BoxedSelf,
+    /// `Vec`, `VecDeque`, `LinkedList`, `HashSet` and `BTreeSet`.
+    Sequence(&'a Type),
+    /// `HashMap` and `BTreeMap`.
+    Map(&'a Type, &'a Type),
+    Option(&'a Type),
+    Result(&'a Type, &'a Type),
+    Array(&'a Type),
+}
+
+impl<'a> Container<'a> {
+    fn from_type(ty: &'a Type, annotated_enum: &ContainerIdent) -> Option<Self> {
+        let type_path = match ty {
+            Type::Array(array) => return Some(Self::Array(&array.elem)),
+            Type::Path(type_path) if type_path.qself.is_none() => type_path,
+            _ => return None,
+        };
+        let segment = type_path.path.segments.last()?;
+        let PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+            return None;
+        };
+        let types = arguments
+            .args
+            .iter()
+            .filter_map(|argument| match argument {
+                GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        match (segment.ident.to_string().as_str(), types.as_slice()) {
+            ("Box", [inner]) if is_self(inner, annotated_enum) => Some(Self::BoxedSelf),
+            ("Vec" | "VecDeque" | "LinkedList" | "BTreeSet", [element])
+            | ("HashSet", [element] | [element, _]) => Some(Self::Sequence(element)),
+            ("BTreeMap", [key, value]) | ("HashMap", [key, value] | [key, value, _]) => {
+                Some(Self::Map(key, value))
+            }
+            ("Option", [inner]) => Some(Self::Option(inner)),
+            ("Result", [ok, err]) => Some(Self::Result(ok, err)),
+            _ => None,
+        }
+    }
+}
+
+/// Whether the type is `Self` or `AnnotatedEnum`.
+fn is_self(ty: &Type, annotated_enum: &ContainerIdent) -> bool {
+    matches!(
+        ty,
+        Type::Path(type_path) if type_path.qself.is_none()
+            && (type_path.path.is_ident("Self") || type_path.path.is_ident(&annotated_enum.0))
+    )
+}
+
+/// Whether the type is `Self` or `AnnotatedEnum`, or a container holding them such as
+/// `Option<Box<Self>>`.
+pub fn contains_self(ty: &Type, annotated_enum: &ContainerIdent) -> bool {
+    is_self(ty, annotated_enum)
+        || match Container::from_type(ty, annotated_enum) {
+            None => false,
+            Some(Container::BoxedSelf) => true,
+            Some(
+                Container::Sequence(element)
+                | Container::Option(element)
+                | Container::Array(element),
+            ) => contains_self(element, annotated_enum),
+            Some(Container::Map(first, second) | Container::Result(first, second)) => {
+                contains_self(first, annotated_enum) || contains_self(second, annotated_enum)
+            }
+        }
+}
+
+/// Generates the expression converting `value` from or into the type `ty` of a field of the
+/// annotated enum.
+/// The content of the standard containers is converted element by element when `map` is set,
+/// since the containers do not implement `From` for a different content type, and the
+/// `Box<Self>` fields are unboxed and converted recursively. Otherwise the value is converted with
+/// `Into`, which may also wrap it in a container, for example in a `Box<Self>`.
+pub fn generate_conversion(
+    value: impl ToTokens,
+    ty: &Type,
+    annotated_enum: &ContainerIdent,
+    map: bool,
+) -> TokenStream {
+    let mapper = |ty| generate_mapper(ty, annotated_enum);
+    let container = map
+        .then(|| Container::from_type(ty, annotated_enum))
+        .flatten();
+    match container {
+        None => quote! { ::core::convert::Into::into(#value) },
+        Some(Container::BoxedSelf) => quote! {
+            ::enum_convert::__private::Box::new(::core::convert::Into::into(*#value))
+        },
+        Some(Container::Sequence(element)) => {
+            let element = mapper(element);
+            quote! {
+                ::core::iter::Iterator::collect(::core::iter::Iterator::map(
+                    ::core::iter::IntoIterator::into_iter(#value),
+                    #element,
+                ))
+            }
+        }
+        Some(Container::Map(key, value_type)) => {
+            let key = generate_conversion(quote! { key }, key, annotated_enum, true);
+            let element = generate_conversion(quote! { element }, value_type, annotated_enum, true);
+            quote! {
+                ::core::iter::Iterator::collect(::core::iter::Iterator::map(
+                    ::core::iter::IntoIterator::into_iter(#value),
+                    |(key, element)| (#key, #element),
+                ))
+            }
+        }
+        Some(Container::Option(element)) | Some(Container::Array(element)) => {
+            let element = mapper(element);
+            quote! { #value.map(#element) }
+        }
+        Some(Container::Result(ok, err)) => {
+            let ok = mapper(ok);
+            let err = mapper(err);
+            quote! { #value.map(#ok).map_err(#err) }
+        }
+    }
+}
+
+/// Generates the expression converting `value` into the type `ty` of a field of the annotated
+/// enum with `FromAsync`, where `ty` contains `Self` and the content of the containers is
+/// converted element by element.
+/// The elements are converted one after the other, since the closures mapping the elements
+/// cannot await, and the recursive futures are boxed to have a known size.
+pub fn generate_async_conversion(
+    value: impl ToTokens,
+    ty: &Type,
+    annotated_enum: &ContainerIdent,
+) -> TokenStream {
+    let convert = |value, ty| generate_async_conversion(value, ty, annotated_enum);
+    if is_self(ty, annotated_enum) {
+        return quote! {
+            ::enum_convert::__private::Box::pin(
+                <Self as ::enum_convert::FromAsync<_>>::from_async(#value),
+            )
+            .await?
+        };
+    }
+    if !contains_self(ty, annotated_enum) {
+        return generate_conversion(value, ty, annotated_enum, true);
+    }
+    let self_type: Type = syn::parse_quote! { Self };
+    match Container::from_type(ty, annotated_enum) {
+        None => unreachable!("The types containing Self are containers"),
+        Some(Container::BoxedSelf) => {
+            let element = convert(quote! { *#value }, &self_type);
+            quote! { ::enum_convert::__private::Box::new(#element) }
+        }
+        Some(Container::Sequence(element)) => {
+            let element = convert(quote! { element }, element);
+            quote! {
+                {
+                    let mut elements: #ty = ::core::default::Default::default();
+                    for element in ::core::iter::IntoIterator::into_iter(#value) {
+                        ::core::iter::Extend::extend(
+                            &mut elements,
+                            ::core::option::Option::Some(#element),
+                        );
+                    }
+                    elements
+                }
+            }
+        }
+        Some(Container::Map(key, value_type)) => {
+            let key = convert(quote! { key }, key);
+            let element = convert(quote! { element }, value_type);
+            quote! {
+                {
+                    let mut elements: #ty = ::core::default::Default::default();
+                    for (key, element) in ::core::iter::IntoIterator::into_iter(#value) {
+                        ::core::iter::Extend::extend(
+                            &mut elements,
+                            ::core::option::Option::Some((#key, #element)),
+                        );
+                    }
+                    elements
+                }
+            }
+        }
+        Some(Container::Option(element)) => {
+            let element = convert(quote! { element }, element);
+            quote! {
+                match #value {
+                    ::core::option::Option::Some(element) => ::core::option::Option::Some(#element),
+                    ::core::option::Option::None => ::core::option::Option::None,
+                }
+            }
+        }
+        Some(Container::Result(ok, err)) => {
+            let ok = convert(quote! { element }, ok);
+            let err = convert(quote! { element }, err);
+            quote! {
+                match #value {
+                    ::core::result::Result::Ok(element) => ::core::result::Result::Ok(#ok),
+                    ::core::result::Result::Err(element) => ::core::result::Result::Err(#err),
+                }
+            }
+        }
+        // The array is rebuilt from a vector of the same length.
+        Some(Container::Array(element)) => {
+            let element = convert(quote! { element }, element);
+            quote! {
+                {
+                    let mut elements = ::enum_convert::__private::Vec::new();
+                    for element in ::core::iter::IntoIterator::into_iter(#value) {
+                        elements.push(#element);
+                    }
+                    match ::core::convert::TryFrom::try_from(elements) {
+                        ::core::result::Result::Ok(elements) => elements,
+                        ::core::result::Result::Err(_) => ::core::unreachable!(),
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Generates the function converting the elements of type `ty` of a container.
+fn generate_mapper(ty: &Type, annotated_enum: &ContainerIdent) -> TokenStream {
+    match Container::from_type(ty, annotated_enum) {
+        None => quote! { ::core::convert::Into::into },
+        Some(_) => {
+            let conversion = generate_conversion(quote! { element }, ty, annotated_enum, true);
+            quote! { |element| #conversion }
+        }
+    }
+}
@@ -0,0 +1,60 @@
+#[automatically_derived]
+impl ::core::convert::From<First> for Target {
+    fn from(value: First) -> Self {
+        match value {
+            First::Unit => Target::Unit,
+            First::Tuple(field_0, field_1) => {
+                Target::Tuple(
+                    ::core::convert::Into::into(field_1),
+                    ::core::convert::Into::into(field_0),
+                )
+            }
+            #[allow(non_snake_case)]
+            First::Point { x, y } => {
+                Target::Point {
+                    abscissa: ::core::convert::Into::into(x),
+                    y: ::core::convert::Into::into(y),
+                }
+            }
+            #[allow(non_snake_case)]
+            First::Status { code } if code >= 500 => {
+                Target::Error {
+                    code: ::core::convert::Into::into(code),
+                }
+            }
+            #[allow(non_snake_case)]
+            First::Status { code } => {
+                Target::Success {
+                    code: ::core::convert::Into::into(code),
+                }
+            }
+        }
+    }
+}
+const _: ::core::option::Option<&str> = ::core::option_env!("ENUM_CONVERT_DUMP_DIR");
+#[automatically_derived]
+impl ::core::convert::From<Second> for Target {
+    fn from(value: Second) -> Self {
+        match value {
+            Second::Empty => Target::Unit,
+            Second::Pair(field_0, field_1) => {
+                Target::Tuple(
+                    ::core::convert::Into::into(field_1),
+                    ::core::convert::Into::into(field_0),
+                )
+            }
+        }
+    }
+}
+const _: ::core::option::Option<&str> = ::core::option_env!("ENUM_CONVERT_DUMP_DIR");
+#[automatically_derived]
+impl ::core::convert::TryFrom<u8> for Target {
+    type Error = u8;
+    fn try_from(value: u8) -> ::core::result::Result<Self, Self::Error> {
+        match value {
+            value if value == (0) as u8 => ::core::result::Result::Ok(Self::Unit),
+            value if value == (5) as u8 => ::core::result::Result::Ok(Self::Other),
+            _ => ::core::result::Result::Err(value),
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use enum_convert::EnumFrom;
+
+enum First {
+    Unit,
+    Tuple(i32, i32),
+    Point { x: i32, y: i32 },
+    Status { code: u16 },
+}
+
+enum Second {
+    Empty,
+    Pair(i32, i32),
+}
+
+#[derive(EnumFrom)]
+#[enum_from(First, Second, u8)]
+enum Target {
+    #[enum_from(First::Unit, Second::Empty)]
+    Unit,
+    #[enum_from(First::Tuple, Second::Pair)]
+    Tuple(
+        #[enum_from(First::Tuple.1, Second::Pair.1)] i64,
+        #[enum_from(First::Tuple.0, Second::Pair.0)] i64,
+    ),
+    #[enum_from(First::Point)]
+    Point {
+        #[enum_from(First::Point.x)]
+        abscissa: i64,
+        y: i64,
+    },
+    #[enum_from(First::Status if code >= 500)]
+    Error { code: u16 },
+    #[enum_from(First::Status)]
+    Success { code: u16 },
+    Other,
+}
@@ -0,0 +1,33 @@
+#[automatically_derived]
+impl ::enum_convert::FromAsync<Source> for Target {
+    type Error = std::io::Error;
+    async fn from_async(value: Source) -> ::core::result::Result<Self, Self::Error> {
+        ::core::result::Result::Ok(
+            match value {
+                Source::Unit => Target::Unit,
+                Source::Name(field_0) => Target::Name(load_name(field_0).await?),
+            },
+        )
+    }
+}
+#[automatically_derived]
+impl ::enum_convert::EnumMappingAsync<Target> for Source {
+    const VARIANTS: &'static [::enum_convert::VariantMapping] = &[
+        ::enum_convert::VariantMapping {
+            source: "Name",
+            target: "Name",
+            fields: &[
+                ::enum_convert::FieldMapping {
+                    source: "0",
+                    target: "0",
+                },
+            ],
+        },
+        ::enum_convert::VariantMapping {
+            source: "Unit",
+            target: "Unit",
+            fields: &[],
+        },
+    ];
+}
+const _: ::core::option::Option<&str> = ::core::option_env!("ENUM_CONVERT_DUMP_DIR");
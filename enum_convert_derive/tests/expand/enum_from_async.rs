@@ -0,0 +1,15 @@
+use enum_convert::EnumFromAsync;
+
+enum Source {
+    Unit,
+    Name(String),
+}
+
+#[derive(EnumFromAsync)]
+#[enum_from(Source, error = std::io::Error, mapping)]
+enum Target {
+    #[enum_from]
+    Unit,
+    #[enum_from]
+    Name(#[enum_from(with = load_name)] String),
+}
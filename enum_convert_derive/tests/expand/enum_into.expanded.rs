@@ -0,0 +1,127 @@
+#[automatically_derived]
+impl ::core::convert::From<Source> for First {
+    fn from(value: Source) -> Self {
+        match value {
+            Source::Unit => First::Unit,
+            Source::Tuple(field_0, field_1) => {
+                First::Pair(
+                    ::core::convert::Into::into(field_0),
+                    ::core::convert::Into::into(field_1),
+                )
+            }
+            Source::Point { abscissa, y } => {
+                First::Point {
+                    x: ::core::convert::Into::into(abscissa),
+                    y: ::core::convert::Into::into(y),
+                }
+            }
+        }
+    }
+}
+#[automatically_derived]
+impl ::enum_convert::EnumMapping<First> for Source {
+    const VARIANTS: &'static [::enum_convert::VariantMapping] = &[
+        ::enum_convert::VariantMapping {
+            source: "Point",
+            target: "Point",
+            fields: &[
+                ::enum_convert::FieldMapping {
+                    source: "abscissa",
+                    target: "x",
+                },
+                ::enum_convert::FieldMapping {
+                    source: "y",
+                    target: "y",
+                },
+            ],
+        },
+        ::enum_convert::VariantMapping {
+            source: "Tuple",
+            target: "Pair",
+            fields: &[
+                ::enum_convert::FieldMapping {
+                    source: "0",
+                    target: "0",
+                },
+                ::enum_convert::FieldMapping {
+                    source: "1",
+                    target: "1",
+                },
+            ],
+        },
+        ::enum_convert::VariantMapping {
+            source: "Unit",
+            target: "Unit",
+            fields: &[],
+        },
+    ];
+}
+const _: ::core::option::Option<&str> = ::core::option_env!("ENUM_CONVERT_DUMP_DIR");
+#[automatically_derived]
+impl ::core::convert::From<Source> for Second {
+    fn from(value: Source) -> Self {
+        match value {
+            Source::Unit => Second::Empty,
+            Source::Tuple(field_0, field_1) => {
+                Second::Pair(
+                    ::core::convert::Into::into(field_0),
+                    ::core::convert::Into::into(field_1),
+                )
+            }
+            Source::Point { abscissa, y } => {
+                Second::Point {
+                    abscissa: ::core::convert::Into::into(abscissa),
+                    y: ::core::convert::Into::into(y),
+                }
+            }
+        }
+    }
+}
+#[automatically_derived]
+impl ::enum_convert::EnumMapping<Second> for Source {
+    const VARIANTS: &'static [::enum_convert::VariantMapping] = &[
+        ::enum_convert::VariantMapping {
+            source: "Point",
+            target: "Point",
+            fields: &[
+                ::enum_convert::FieldMapping {
+                    source: "abscissa",
+                    target: "abscissa",
+                },
+                ::enum_convert::FieldMapping {
+                    source: "y",
+                    target: "y",
+                },
+            ],
+        },
+        ::enum_convert::VariantMapping {
+            source: "Tuple",
+            target: "Pair",
+            fields: &[
+                ::enum_convert::FieldMapping {
+                    source: "0",
+                    target: "0",
+                },
+                ::enum_convert::FieldMapping {
+                    source: "1",
+                    target: "1",
+                },
+            ],
+        },
+        ::enum_convert::VariantMapping {
+            source: "Unit",
+            target: "Empty",
+            fields: &[],
+        },
+    ];
+}
+const _: ::core::option::Option<&str> = ::core::option_env!("ENUM_CONVERT_DUMP_DIR");
+#[automatically_derived]
+impl ::core::convert::From<Opcode> for u8 {
+    fn from(value: Opcode) -> Self {
+        match value {
+            Opcode::Ping => (0) as u8,
+            Opcode::Close => 0xff,
+        }
+    }
+}
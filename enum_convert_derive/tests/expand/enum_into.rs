@@ -0,0 +1,35 @@
+use enum_convert::EnumInto;
+
+enum First {
+    Unit,
+    Pair(i64, i64),
+    Point { x: i64, y: i64 },
+}
+
+enum Second {
+    Empty,
+    Pair(i64, i64),
+}
+
+#[derive(EnumInto)]
+#[enum_into(First, Second, mapping)]
+enum Source {
+    #[enum_into(First::Unit, Second::Empty)]
+    Unit,
+    #[enum_into(First::Pair, Second::Pair)]
+    Tuple(i32, i32),
+    #[enum_into(First::Point)]
+    Point {
+        #[enum_into(First::Point.x)]
+        abscissa: i32,
+        y: i32,
+    },
+}
+
+#[derive(EnumInto)]
+#[enum_into(u8)]
+enum Opcode {
+    Ping,
+    #[enum_into(u8 = 0xff)]
+    Close,
+}
@@ -0,0 +1,65 @@
+use std::{
+    pin::pin,
+    task::{Context, Poll, Waker},
+};
+
+use enum_convert::{
+    EnumFrom, EnumFromAsync, EnumMapping, EnumMappingAsync, FieldMapping, FromAsync, VariantMapping,
+};
+
+enum Source {
+    Unit,
+    Struct { x: i32, y: i32 },
+}
+
+#[derive(EnumFrom, EnumFromAsync)]
+#[enum_from(Source, mapping)]
+enum Target {
+    #[enum_from]
+    Unit,
+    #[enum_from(Source::Struct)]
+    Point {
+        #[enum_from(Source::Struct.x)]
+        a: i64,
+        y: i64,
+    },
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    match pin!(future).poll(&mut Context::from_waker(Waker::noop())) {
+        Poll::Ready(output) => output,
+        Poll::Pending => panic!("The future should be ready"),
+    }
+}
+
+fn main() {
+    assert!(matches!(Target::from(Source::Unit), Target::Unit));
+    assert!(matches!(
+        block_on(Target::from_async(Source::Struct { x: 1, y: 2 })),
+        Ok(Target::Point { a: 1, y: 2 }),
+    ));
+
+    let variants = &[
+        VariantMapping {
+            source: "Struct",
+            target: "Point",
+            fields: &[
+                FieldMapping {
+                    source: "x",
+                    target: "a",
+                },
+                FieldMapping {
+                    source: "y",
+                    target: "y",
+                },
+            ],
+        },
+        VariantMapping {
+            source: "Unit",
+            target: "Unit",
+            fields: &[],
+        },
+    ];
+    assert_eq!(<Source as EnumMapping<Target>>::VARIANTS, variants);
+    assert_eq!(<Source as EnumMappingAsync<Target>>::VARIANTS, variants);
+}
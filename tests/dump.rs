@@ -0,0 +1,70 @@
+use std::{
+    fs,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Builds the test crates with `ENUM_CONVERT_DUMP_DIR` set and compares the dumped files with the
+/// expected ones.
+#[test]
+fn dump_tests() {
+    // The compiler tracks the variable, so a new directory rebuilds the test crates.
+    let run = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    let dump_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join(format!("dump-{run}"));
+    // SAFETY: this test binary has no other thread reading the environment.
+    unsafe { std::env::set_var("ENUM_CONVERT_DUMP_DIR", &dump_dir) };
+
+    let t = trybuild::TestCases::new();
+    t.pass("tests/dump/pass/**/*.rs");
+    // The test crates are built when the test cases are dropped.
+    drop(t);
+
+    let expected_dir = Path::new("tests/dump/expected");
+    // The dependencies deriving conversions, such as `no_std_enums`, are dumped too.
+    let mut dumped = fs::read_dir(&dump_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .filter(|file_name| file_name.starts_with("trybuild"))
+        .map(|file_name| {
+            let normalized = normalize(&file_name);
+            (normalized, file_name)
+        })
+        .collect::<Vec<_>>();
+    dumped.sort();
+    let mut expected = fs::read_dir(expected_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect::<Vec<_>>();
+    expected.sort();
+    assert_eq!(
+        dumped
+            .iter()
+            .map(|(normalized, _)| normalized)
+            .collect::<Vec<_>>(),
+        expected.iter().collect::<Vec<_>>(),
+    );
+    for (normalized, file_name) in dumped {
+        // The name of the test crate is chosen by trybuild.
+        let crate_name = file_name.split('.').next().unwrap();
+        assert_eq!(
+            fs::read_to_string(dump_dir.join(&file_name))
+                .unwrap()
+                .replace(crate_name, "$CRATE"),
+            fs::read_to_string(expected_dir.join(&normalized)).unwrap(),
+            "{file_name:?} differs from the expected dump",
+        );
+    }
+    fs::remove_dir_all(&dump_dir).unwrap();
+}
+
+/// Removes the name of the test crate and the hash of the location of the annotated enum, which
+/// depends on the path of the test file, from `<crate>.<Source>.<Target>.<hash>.<extension>`.
+fn normalize(file_name: &str) -> String {
+    let [_, source, target, _, extension] = file_name.split('.').collect::<Vec<_>>()[..] else {
+        panic!("unexpected dump {file_name:?}");
+    };
+    format!("{source}.{target}.{extension}")
+}
@@ -0,0 +1,51 @@
+use enum_convert::{EnumFrom, EnumInto};
+
+#[derive(Debug)]
+enum Source {
+    Unit,
+    Tuple(i32, String),
+    Struct { x: i32, y: i32 },
+}
+
+#[derive(Debug, EnumFrom)]
+#[enum_from(Source)]
+enum Target {
+    #[enum_from]
+    Unit,
+    #[enum_from(Source::Tuple)]
+    Pair(i32, String),
+    #[enum_from]
+    Struct {
+        #[enum_from(Source::Struct.x)]
+        left: i32,
+        y: i32,
+    },
+}
+
+#[enum_convert::register]
+enum Registers {
+    First = 1,
+    Second,
+}
+
+#[derive(Debug, EnumFrom)]
+#[enum_from(Registers { First, Second }, by_discriminant, registered)]
+enum Code {
+    High = 2,
+    Low = 1,
+}
+
+#[derive(Debug, EnumInto)]
+#[enum_into(Source)]
+enum Narrow {
+    #[enum_into]
+    Unit,
+    #[enum_into(Source::Tuple)]
+    Single(i32, String),
+}
+
+fn main() {
+    let _ = Target::from(Source::Unit);
+    let _ = Source::from(Narrow::Unit);
+    let _ = Code::from(Registers::Second);
+}
@@ -0,0 +1,16 @@
+use enum_convert::EnumFrom;
+
+enum Source {
+    A,
+    B,
+}
+
+#[derive(EnumFrom)]
+#[enum_from(Source, by_discriminant)]
+enum Target {
+    #[enum_from]
+    A,
+    B,
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+use enum_convert::EnumFrom;
+
+enum Source {
+    A = 1,
+    B = 2,
+    C = 3,
+}
+
+#[derive(EnumFrom)]
+#[enum_from(Source { A, B, C }, by_discriminant)]
+enum Target {
+    A = 1,
+    B = 2,
+    #[enum_from(skip)]
+    C = 3,
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use enum_convert::EnumFrom;
+
+enum Source {
+    Code(u16),
+    Other,
+}
+
+#[derive(EnumFrom)]
+#[enum_from(Source { Code, Other })] // Only Source::Code(404) is converted
+enum Target {
+    #[enum_from(Source::Code(404))]
+    NotFound,
+    #[enum_from]
+    Other,
+}
+
+fn main() {}
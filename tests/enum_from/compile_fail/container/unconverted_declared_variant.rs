@@ -0,0 +1,18 @@
+use enum_convert::EnumFrom;
+
+enum Source {
+    Unit,
+    Tuple(i32),
+    Struct { x: i32 },
+}
+
+#[derive(EnumFrom)]
+#[enum_from(Source { Unit, Tuple, Struct })] // Source::Struct is not converted
+enum Target {
+    #[enum_from]
+    Unit,
+    #[enum_from]
+    Tuple(i32),
+}
+
+fn main() {}
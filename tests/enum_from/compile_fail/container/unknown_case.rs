@@ -0,0 +1,14 @@
+use enum_convert::EnumFrom;
+
+enum Source {
+    Unit,
+}
+
+#[derive(EnumFrom)]
+#[enum_from(Source, rename_all = "kebab-case")]
+enum Target {
+    #[enum_from]
+    Unit,
+}
+
+fn main() {}
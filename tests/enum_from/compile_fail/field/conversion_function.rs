@@ -0,0 +1,18 @@
+use enum_convert::EnumFrom;
+
+enum Source {
+    Tuple(i32),
+}
+
+async fn convert(value: i32) -> Result<i64, String> {
+    Ok(value.into())
+}
+
+#[derive(EnumFrom)]
+#[enum_from(Source)]
+enum Target {
+    #[enum_from]
+    Tuple(#[enum_from(with = convert)] i64), // Only supported by EnumFromAsync
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use enum_convert::EnumFrom;
+
+enum Source {
+    Tuple(Vec<i32>),
+}
+
+#[derive(EnumFrom)]
+#[enum_from(Source)]
+enum Target {
+    #[enum_from]
+    Tuple(#[enum_from(map, as_is)] Vec<i64>),
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use enum_convert::EnumFrom;
+
+enum Source {
+    Struct { a: i32, b: i32 },
+}
+
+#[derive(EnumFrom)]
+#[enum_from(Source)]
+enum Target {
+    #[enum_from(Source::Struct)]
+    Tuple(
+        #[enum_from(Source::Struct.a)] i32,
+        #[enum_from(Source::Struct.1)] i32, // .1 Does not make sense for a Struct source
+    ),
+}
+
+fn main() {}
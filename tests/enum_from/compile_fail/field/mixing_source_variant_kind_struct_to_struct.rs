@@ -0,0 +1,19 @@
+use enum_convert::EnumFrom;
+
+enum Source {
+    Struct { a: i32, b: i32 },
+}
+
+#[derive(EnumFrom)]
+#[enum_from(Source)]
+enum Target {
+    #[enum_from(Source::Struct)]
+    Struct {
+        #[enum_from(Source::Struct.b)]
+        a: i32,
+        #[enum_from(Source::Struct.0)] // .0 Does not make sense for a Struct source
+        b: i32,
+    },
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use enum_convert::EnumFrom;
+
+enum Source {
+    Tuple(i32, i32),
+}
+
+#[derive(EnumFrom)]
+#[enum_from(Source)]
+enum Target {
+    #[enum_from(Source::Tuple)]
+    Tuple(
+        #[enum_from(Source::Tuple.1)] i32,
+        #[enum_from(Source::Tuple.a)] i32, // .a Does not make sense for a Tuple source
+    ),
+}
+
+fn main() {}
@@ -0,0 +1,23 @@
+use enum_convert::EnumFrom;
+
+enum Source {
+    Tuple(i32, i32),
+    Struct { x: i32 },
+}
+
+#[derive(EnumFrom)]
+#[enum_from(Source)]
+enum Target {
+    #[enum_from]
+    Tuple(
+        #[enum_from(Source::Tuple.0, unknown)] i32,
+        #[enum_from(invalid syntax)] i32,
+    ),
+    #[enum_from(Source::Other)]
+    Struct {
+        #[enum_from(Source::Struct.x, unknown)]
+        x: i32,
+    },
+}
+
+fn main() {}
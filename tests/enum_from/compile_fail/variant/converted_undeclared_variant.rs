@@ -0,0 +1,17 @@
+use enum_convert::EnumFrom;
+
+enum Source {
+    Unit,
+    Struct { x: i32 },
+}
+
+#[derive(EnumFrom)]
+#[enum_from(Source { Unit, Struct })]
+enum Target {
+    #[enum_from]
+    Unit,
+    #[enum_from(Source::Strcut)] // Should be Source::Struct
+    Struct { x: i32 },
+}
+
+fn main() {}
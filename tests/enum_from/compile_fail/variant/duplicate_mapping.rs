@@ -0,0 +1,16 @@
+use enum_convert::EnumFrom;
+
+enum Source {
+    Unit,
+}
+
+#[derive(EnumFrom)]
+#[enum_from(Source)]
+enum Target {
+    #[enum_from]
+    Unit,
+    #[enum_from(Source::Unit)] // Source::Unit is already mapped to Target::Unit
+    Other,
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+use enum_convert::EnumFrom;
+
+#[allow(non_upper_case_globals)]
+const limit: u16 = 500;
+
+enum Source {
+    Http { code: u16 },
+}
+
+#[derive(EnumFrom)]
+#[enum_from(Source)]
+enum Target {
+    #[enum_from(Source::Http if code >= limit)] // Should be self::limit
+    ServerError,
+    #[enum_from(Source::Http { .. })]
+    Other,
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use enum_convert::EnumFrom;
+
+enum Source {
+    Http { code: u16 },
+}
+
+#[derive(EnumFrom)]
+#[enum_from(Source)]
+enum Target {
+    #[enum_from(Source::Http if code >= 500)]
+    ServerError { code: u16 },
+    #[enum_from(Source::Http if code >= 400)] // Missing a mapping without guard
+    ClientError { code: u16 },
+}
+
+fn main() {}
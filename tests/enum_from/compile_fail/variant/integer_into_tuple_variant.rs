@@ -0,0 +1,11 @@
+use enum_convert::EnumFrom;
+
+#[derive(EnumFrom)]
+#[enum_from(u8)]
+enum Target {
+    Unit,
+    #[enum_from(u8 = 1)]
+    Tuple(u8),
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use enum_convert::EnumFrom;
+
+enum Source {
+    Code(u16),
+}
+
+#[derive(EnumFrom)]
+#[enum_from(Source)]
+enum Target {
+    #[enum_from(Source::Code(404 +))] // Not a pattern
+    NotFound,
+    #[enum_from]
+    Code(u16),
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use enum_convert::EnumFrom;
+
+enum Source {
+    Unit,
+}
+
+#[derive(EnumFrom)]
+#[enum_from(Source, all)]
+enum Target {
+    Unit,
+    Extra, // Should be marked with #[enum_from(skip)]
+}
+
+fn main() {}
@@ -0,0 +1,23 @@
+use enum_convert::EnumFrom;
+
+enum Source {
+    Unit,
+    Tuple(i32),
+    Struct { x: i32 },
+}
+
+#[derive(EnumFrom)]
+#[enum_from(Source)]
+enum Target {
+    #[enum_from(Sourse::Unit)] // Should be Source::Unit
+    Unit,
+    #[enum_from]
+    Tuple(#[enum_from(Soruce::Tuple.0)] i32), // Should be Source::Tuple.0
+    #[enum_from(Source::Struct)]
+    Struct {
+        #[enum_from(Source::Strcut.x)] // Should be Source::Struct.x
+        a: i32,
+    },
+}
+
+fn main() {}
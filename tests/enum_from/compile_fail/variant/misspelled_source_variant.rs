@@ -0,0 +1,14 @@
+use enum_convert::EnumFrom;
+
+enum Source {
+    InvalidConfiguration,
+}
+
+#[derive(EnumFrom)]
+#[enum_from(Source)]
+enum Target {
+    #[enum_from(Source::InvalidConfiguraton)] // Should be Source::InvalidConfiguration
+    InvalidConfiguration,
+}
+
+fn main() {}
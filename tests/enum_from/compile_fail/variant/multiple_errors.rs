@@ -0,0 +1,24 @@
+use enum_convert::EnumFrom;
+
+enum Source {
+    Unit,
+    Tuple(i32),
+    Struct { x: i32 },
+}
+
+#[derive(EnumFrom)]
+#[enum_from(Source)]
+enum Target {
+    #[enum_from(Source, NonExistent)] // Invalid source enum
+    Unit,
+    #[enum_from(Source::Tuple, Other::Tuple)] // Invalid source enum
+    Tuple(i32),
+    #[enum_from]
+    Struct {
+        // Should be #[enum_from(Source::Struct.x)]
+        #[enum_from(NonExistent::Struct.x)]
+        a: i32,
+    },
+}
+
+fn main() {}
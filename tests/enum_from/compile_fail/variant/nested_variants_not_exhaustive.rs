@@ -0,0 +1,19 @@
+use enum_convert::EnumFrom;
+
+enum IoKind {
+    NotFound,
+    Denied,
+}
+
+enum Source {
+    Io(IoKind),
+}
+
+#[derive(EnumFrom)]
+#[enum_from(Source)]
+enum Target {
+    #[enum_from(Source::Io(IoKind::NotFound))] // IoKind::Denied is not mapped
+    NotFound,
+}
+
+fn main() {}
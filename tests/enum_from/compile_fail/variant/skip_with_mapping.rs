@@ -0,0 +1,14 @@
+use enum_convert::EnumFrom;
+
+enum Source {
+    Unit,
+}
+
+#[derive(EnumFrom)]
+#[enum_from(Source, all)]
+enum Target {
+    #[enum_from(Source::Unit, skip)]
+    Unit,
+}
+
+fn main() {}
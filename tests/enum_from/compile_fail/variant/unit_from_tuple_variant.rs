@@ -0,0 +1,14 @@
+use enum_convert::EnumFrom;
+
+enum Source {
+    Tuple(i32),
+}
+
+#[derive(EnumFrom)]
+#[enum_from(Source)]
+enum Target {
+    #[enum_from(Source::Tuple)] // A unit variant has no field to convert the payload into
+    Unit,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use enum_convert::EnumFrom;
+
+#[derive(EnumFrom)]
+#[enum_from(u8)]
+enum Target {
+    #[enum_from(i8 = 1)] // Should be u8
+    Unit,
+}
+
+fn main() {}
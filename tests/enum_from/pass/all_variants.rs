@@ -0,0 +1,42 @@
+use enum_convert::EnumFrom;
+
+enum Source {
+    Unit,
+    Tuple(i32, &'static str),
+    Struct { x: i32, y: i32 },
+    Other,
+}
+
+enum OtherSource {
+    Unit,
+    Tuple(i32, &'static str),
+    Struct { x: i32, y: i32 },
+}
+
+#[derive(Debug, PartialEq, EnumFrom)]
+#[enum_from(Source, OtherSource, all)]
+enum Target {
+    Unit,
+    Tuple(i64, String),
+    Struct {
+        x: i64,
+        y: i64,
+    },
+    #[enum_from(Source::Other)]
+    Renamed,
+    #[enum_from(skip)]
+    Extra,
+}
+
+fn main() {
+    assert_eq!(Target::from(Source::Unit), Target::Unit);
+    assert_eq!(
+        Target::from(Source::Tuple(1, "hello")),
+        Target::Tuple(1, "hello".to_string())
+    );
+    assert_eq!(
+        Target::from(OtherSource::Struct { x: 1, y: 2 }),
+        Target::Struct { x: 1, y: 2 }
+    );
+    assert_eq!(Target::from(Source::Other), Target::Renamed);
+}
@@ -0,0 +1,48 @@
+use enum_convert::EnumFrom;
+
+#[derive(Debug, PartialEq)]
+struct Id(u32);
+
+enum Source {
+    Created { id: Id, name: &'static str },
+    Deleted(Id, u8),
+}
+
+enum OtherSource {
+    Deleted(Id, u8),
+}
+
+#[derive(Debug, PartialEq, EnumFrom)]
+#[enum_from(Source)]
+#[enum_from(OtherSource, as_is)]
+enum Target {
+    #[enum_from(Source::Created)]
+    Created {
+        #[enum_from(as_is)]
+        id: Id,
+        name: String,
+    },
+    #[enum_from(Source::Deleted, OtherSource::Deleted)]
+    Deleted(#[enum_from(as_is)] Id, u8),
+}
+
+fn main() {
+    assert_eq!(
+        Target::from(Source::Created {
+            id: Id(1),
+            name: "name"
+        }),
+        Target::Created {
+            id: Id(1),
+            name: "name".to_string()
+        },
+    );
+    assert_eq!(
+        Target::from(Source::Deleted(Id(2), 3)),
+        Target::Deleted(Id(2), 3)
+    );
+    assert_eq!(
+        Target::from(OtherSource::Deleted(Id(4), 5)),
+        Target::Deleted(Id(4), 5)
+    );
+}
@@ -0,0 +1,22 @@
+use enum_convert::EnumFrom;
+
+enum Command {
+    Run(&'static str),
+    Retry(Task), // Converted into the Box with Box<T>: From<T>
+}
+
+#[derive(Debug, PartialEq, EnumFrom)]
+#[enum_from(Command)]
+enum Task {
+    #[enum_from]
+    Run(String),
+    #[enum_from]
+    Retry(Box<Self>),
+}
+
+fn main() {
+    assert_eq!(
+        Task::from(Command::Retry(Task::Run("build".to_string()))),
+        Task::Retry(Box::new(Task::Run("build".to_string())))
+    );
+}
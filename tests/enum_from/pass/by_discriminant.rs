@@ -0,0 +1,35 @@
+use enum_convert::EnumFrom;
+
+// The same register codes named differently by two vendors.
+enum VendorA {
+    Status = 0x01,
+    Control = 0x02,
+    Data = 0x10,
+}
+
+#[repr(u8)]
+enum VendorB {
+    Config = 2,
+    Payload = 16,
+    State = 1,
+}
+
+#[derive(Debug, PartialEq, EnumFrom)]
+#[enum_from(VendorA { Status, Control, Data }, VendorB { Config, Payload, State }, by_discriminant)]
+enum Register {
+    Status = 0x01,
+    Control, // 0x02
+    Data = 0x10,
+    #[enum_from(skip)]
+    Reserved = 0x20,
+}
+
+fn main() {
+    assert_eq!(Register::from(VendorA::Status), Register::Status);
+    assert_eq!(Register::from(VendorA::Control), Register::Control);
+    assert_eq!(Register::from(VendorA::Data), Register::Data);
+
+    assert_eq!(Register::from(VendorB::State), Register::Status);
+    assert_eq!(Register::from(VendorB::Config), Register::Control);
+    assert_eq!(Register::from(VendorB::Payload), Register::Data);
+}
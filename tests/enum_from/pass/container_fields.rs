@@ -0,0 +1,97 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use enum_convert::EnumFrom;
+
+#[derive(Debug, PartialEq)]
+struct Small(i32);
+
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+struct Big(i64);
+
+impl From<Small> for Big {
+    fn from(Small(value): Small) -> Self {
+        Big(value.into())
+    }
+}
+
+enum Source {
+    Sequences(Vec<Small>, HashSet<i32>),
+    Maps {
+        by_name: HashMap<String, Small>,
+        sorted: BTreeMap<i32, Vec<Small>>,
+    },
+    Optional(Option<Small>, Option<Option<i32>>),
+    Fallible(Result<Small, &'static str>),
+    Array([Small; 2]),
+    Expr(Vec<Source>, Option<Box<Source>>),
+}
+
+#[derive(Debug, PartialEq, EnumFrom)]
+#[enum_from(Source)]
+enum Target {
+    #[enum_from]
+    Sequences(#[enum_from(map)] Vec<Big>, #[enum_from(map)] HashSet<i64>),
+    #[enum_from]
+    Maps {
+        #[enum_from(map)]
+        by_name: HashMap<String, Big>,
+        #[enum_from(map)]
+        sorted: BTreeMap<i64, Vec<Big>>,
+    },
+    #[enum_from]
+    Optional(
+        #[enum_from(map)] Option<Big>,
+        #[enum_from(map)] Option<Option<i64>>,
+    ),
+    #[enum_from]
+    Fallible(#[enum_from(map)] Result<Big, String>),
+    #[enum_from]
+    Array(#[enum_from(map)] [Big; 2]),
+    #[enum_from]
+    Expr(
+        #[enum_from(map)] Vec<Target>,
+        #[enum_from(map)] Option<Box<Target>>,
+    ),
+}
+
+fn main() {
+    assert_eq!(
+        Target::from(Source::Sequences(
+            vec![Small(1), Small(2)],
+            HashSet::from([3])
+        )),
+        Target::Sequences(vec![Big(1), Big(2)], HashSet::from([3])),
+    );
+    assert_eq!(
+        Target::from(Source::Maps {
+            by_name: HashMap::from([("one".to_string(), Small(1))]),
+            sorted: BTreeMap::from([(2, vec![Small(2)])]),
+        }),
+        Target::Maps {
+            by_name: HashMap::from([("one".to_string(), Big(1))]),
+            sorted: BTreeMap::from([(2, vec![Big(2)])]),
+        },
+    );
+    assert_eq!(
+        Target::from(Source::Optional(Some(Small(1)), Some(None))),
+        Target::Optional(Some(Big(1)), Some(None)),
+    );
+    assert_eq!(
+        Target::from(Source::Fallible(Err("failed"))),
+        Target::Fallible(Err("failed".to_string())),
+    );
+    assert_eq!(
+        Target::from(Source::Array([Small(1), Small(2)])),
+        Target::Array([Big(1), Big(2)]),
+    );
+    assert_eq!(
+        Target::from(Source::Expr(
+            vec![Source::Optional(None, None)],
+            Some(Box::new(Source::Array([Small(1), Small(2)]))),
+        )),
+        Target::Expr(
+            vec![Target::Optional(None, None)],
+            Some(Box::new(Target::Array([Big(1), Big(2)]))),
+        ),
+    );
+}
@@ -0,0 +1,23 @@
+use enum_convert::EnumFrom;
+
+enum Source {
+    Unit,
+    Point { x: i32, y: i32 },
+}
+
+#[derive(Debug, EnumFrom)]
+#[enum_from(Source, debug)]
+enum Target {
+    #[enum_from]
+    Unit,
+    #[enum_from]
+    Point { x: i64, y: i64 },
+}
+
+fn main() {
+    assert!(matches!(Target::from(Source::Unit), Target::Unit));
+    assert!(matches!(
+        Target::from(Source::Point { x: 1, y: 2 }),
+        Target::Point { x: 1, y: 2 },
+    ));
+}
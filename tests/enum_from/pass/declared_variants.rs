@@ -0,0 +1,42 @@
+use enum_convert::EnumFrom;
+
+enum Source {
+    Unit,
+    Tuple(i32, &'static str),
+    Struct { x: i32, y: i32 },
+    Code(u16),
+}
+
+enum OtherSource {
+    Unit,
+}
+
+#[derive(Debug, PartialEq, EnumFrom)]
+#[enum_from(Source { Unit, Tuple, Struct, Code }, OtherSource)]
+enum Target {
+    #[enum_from(Source, OtherSource)]
+    Unit,
+    #[enum_from(Source)]
+    Tuple(i64, String),
+    #[enum_from(Source)]
+    Struct { x: i64, y: i64 },
+    #[enum_from(Source::Code(404))]
+    NotFound,
+    #[enum_from(Source::Code)]
+    Code(u16),
+}
+
+fn main() {
+    assert_eq!(Target::from(Source::Unit), Target::Unit);
+    assert_eq!(Target::from(OtherSource::Unit), Target::Unit);
+    assert_eq!(
+        Target::from(Source::Tuple(1, "hello")),
+        Target::Tuple(1, "hello".to_string())
+    );
+    assert_eq!(
+        Target::from(Source::Struct { x: 1, y: 2 }),
+        Target::Struct { x: 1, y: 2 }
+    );
+    assert_eq!(Target::from(Source::Code(404)), Target::NotFound);
+    assert_eq!(Target::from(Source::Code(500)), Target::Code(500));
+}
@@ -0,0 +1,71 @@
+use enum_convert::EnumFrom;
+
+#[allow(non_snake_case)]
+enum Source {
+    UserCreated {
+        userId: u32,
+        displayName: &'static str,
+        isAdmin: bool,
+    },
+    UserDeleted {
+        userId: u32,
+        reason: &'static str,
+    },
+}
+
+enum PrefixedSource {
+    UserDeleted { m_user_id: u32, m_cause: String },
+}
+
+#[derive(Debug, PartialEq, EnumFrom)]
+#[enum_from(Source, rename_all_fields = "camelCase")]
+#[enum_from(PrefixedSource, add_field_prefix = "m_")]
+enum Event {
+    #[enum_from(Source::UserCreated)]
+    UserCreated {
+        user_id: u64,
+        display_name: String,
+        is_admin: bool,
+    },
+    #[enum_from(Source::UserDeleted, PrefixedSource::UserDeleted)]
+    UserDeleted {
+        user_id: u64,
+        #[enum_from(Source::UserDeleted.reason, PrefixedSource::UserDeleted.m_cause)]
+        cause: String,
+    },
+}
+
+fn main() {
+    assert_eq!(
+        Event::from(Source::UserCreated {
+            userId: 1,
+            displayName: "name",
+            isAdmin: true,
+        }),
+        Event::UserCreated {
+            user_id: 1,
+            display_name: "name".to_string(),
+            is_admin: true,
+        },
+    );
+    assert_eq!(
+        Event::from(Source::UserDeleted {
+            userId: 2,
+            reason: "spam",
+        }),
+        Event::UserDeleted {
+            user_id: 2,
+            cause: "spam".to_string(),
+        },
+    );
+    assert_eq!(
+        Event::from(PrefixedSource::UserDeleted {
+            m_user_id: 3,
+            m_cause: "spam".to_string(),
+        }),
+        Event::UserDeleted {
+            user_id: 3,
+            cause: "spam".to_string(),
+        },
+    );
+}
@@ -0,0 +1,23 @@
+use enum_convert::EnumFrom;
+
+enum Source {
+    Rotated(i32, i32, i32),
+}
+
+#[derive(Debug, PartialEq, EnumFrom)]
+#[enum_from(Source)]
+enum Target {
+    #[enum_from]
+    Rotated(
+        #[enum_from(Source::Rotated.1)] i32,
+        #[enum_from(Source::Rotated.2)] i32,
+        #[enum_from(Source::Rotated.0)] i32,
+    ),
+}
+
+fn main() {
+    assert_eq!(
+        Target::from(Source::Rotated(1, 2, 3)),
+        Target::Rotated(2, 3, 1)
+    );
+}
@@ -0,0 +1,49 @@
+use enum_convert::EnumFrom;
+
+enum Source {
+    Http { code: u16, message: &'static str },
+    Io(i32),
+    Closed,
+}
+
+#[derive(EnumFrom)]
+#[enum_from(Source)]
+enum Target {
+    #[enum_from(Source::Http if code >= 500)]
+    ServerError { code: u16, message: String },
+    #[enum_from(Source::Http if code >= 400 && !message.is_empty())]
+    ClientError { code: u16, message: String },
+    #[enum_from(Source::Http)]
+    Http { code: u32, message: String },
+    #[enum_from(Source::Io if field_0 < 0)]
+    InvalidIo(i32),
+    #[enum_from]
+    Io(i64),
+    #[enum_from]
+    Closed,
+}
+
+fn main() {
+    assert!(matches!(
+        Target::from(Source::Http { code: 503, message: "unavailable" }),
+        Target::ServerError { code: 503, ref message } if message == "unavailable",
+    ));
+    assert!(matches!(
+        Target::from(Source::Http { code: 404, message: "not found" }),
+        Target::ClientError { code: 404, ref message } if message == "not found",
+    ));
+    assert!(matches!(
+        Target::from(Source::Http { code: 404, message: "" }),
+        Target::Http { code: 404, ref message } if message.is_empty(),
+    ));
+    assert!(matches!(
+        Target::from(Source::Http { code: 200, message: "ok" }),
+        Target::Http { code: 200, ref message } if message == "ok",
+    ));
+    assert!(matches!(
+        Target::from(Source::Io(-1)),
+        Target::InvalidIo(-1)
+    ));
+    assert!(matches!(Target::from(Source::Io(1)), Target::Io(1)));
+    assert!(matches!(Target::from(Source::Closed), Target::Closed));
+}
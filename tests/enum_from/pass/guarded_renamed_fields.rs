@@ -0,0 +1,65 @@
+use enum_convert::EnumFrom;
+
+#[allow(non_snake_case)]
+enum Source {
+    Status { code: u16 },
+    UserDeleted { userId: u32, reason: &'static str },
+}
+
+#[derive(Debug, PartialEq, EnumFrom)]
+#[enum_from(Source, rename_all_fields = "camelCase")]
+enum Target {
+    #[enum_from(Source::Status if code >= 500)]
+    Error {
+        #[enum_from(Source::Status.code)]
+        status: u16,
+    },
+    #[enum_from(Source::Status)]
+    Status {
+        #[enum_from(Source::Status.code)]
+        status: u32,
+    },
+    #[enum_from(Source::UserDeleted if userId == 0 && reason.is_empty())]
+    RootDeleted {
+        user_id: u32,
+        #[enum_from(Source::UserDeleted.reason)]
+        cause: String,
+    },
+    #[enum_from(Source::UserDeleted)]
+    UserDeleted {
+        user_id: u64,
+        #[enum_from(Source::UserDeleted.reason)]
+        cause: String,
+    },
+}
+
+fn main() {
+    assert_eq!(
+        Target::from(Source::Status { code: 503 }),
+        Target::Error { status: 503 }
+    );
+    assert_eq!(
+        Target::from(Source::Status { code: 200 }),
+        Target::Status { status: 200 }
+    );
+    assert_eq!(
+        Target::from(Source::UserDeleted {
+            userId: 0,
+            reason: ""
+        }),
+        Target::RootDeleted {
+            user_id: 0,
+            cause: String::new()
+        },
+    );
+    assert_eq!(
+        Target::from(Source::UserDeleted {
+            userId: 1,
+            reason: "spam"
+        }),
+        Target::UserDeleted {
+            user_id: 1,
+            cause: "spam".to_string()
+        },
+    );
+}
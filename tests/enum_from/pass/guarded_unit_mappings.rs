@@ -0,0 +1,71 @@
+use enum_convert::EnumFrom;
+
+enum Source {
+    Http { code: u16, message: &'static str },
+    Io(i32, &'static str),
+    Closed,
+}
+
+fn is_fatal(code: i32) -> bool {
+    code < -100
+}
+
+#[allow(non_upper_case_globals)]
+const server_errors: u16 = 500;
+
+#[derive(Debug, PartialEq, EnumFrom)]
+#[enum_from(Source)]
+enum Target {
+    #[enum_from(Source::Http if code >= self::server_errors)]
+    ServerError,
+    #[enum_from(Source::Http if message.chars().any(|c| c.is_ascii_digit()))]
+    NumberedError,
+    #[enum_from(Source::Http { .. })]
+    Other,
+    #[enum_from(Source::Io if is_fatal(field_0) || field_1.is_empty())]
+    FatalIo,
+    #[enum_from(Source::Io)]
+    Io(i64, String),
+    #[enum_from(Source::Closed if cfg!(unix))]
+    ClosedOnUnix,
+    #[enum_from(Source::Closed)]
+    Closed,
+}
+
+fn main() {
+    assert_eq!(
+        Target::from(Source::Http {
+            code: 503,
+            message: "unavailable"
+        }),
+        Target::ServerError
+    );
+    assert_eq!(
+        Target::from(Source::Http {
+            code: 404,
+            message: "error 42"
+        }),
+        Target::NumberedError
+    );
+    assert_eq!(
+        Target::from(Source::Http {
+            code: 404,
+            message: "not found"
+        }),
+        Target::Other
+    );
+    assert_eq!(Target::from(Source::Io(-101, "disk")), Target::FatalIo);
+    assert_eq!(Target::from(Source::Io(-1, "")), Target::FatalIo);
+    assert_eq!(
+        Target::from(Source::Io(-1, "disk")),
+        Target::Io(-1, "disk".to_string())
+    );
+    assert_eq!(
+        Target::from(Source::Closed),
+        if cfg!(unix) {
+            Target::ClosedOnUnix
+        } else {
+            Target::Closed
+        }
+    );
+}
@@ -0,0 +1,46 @@
+use enum_convert::EnumFrom;
+
+#[derive(Debug, PartialEq)]
+enum Source {
+    Ping,
+    Data(u32),
+}
+
+const BASE: u8 = 0x20;
+
+#[derive(Debug, PartialEq, EnumFrom)]
+#[enum_from(Source, u8, u16)]
+#[repr(u8)]
+enum Message {
+    #[enum_from]
+    Ping, // 0
+    Pong, // 1
+    Reset = 0x10,
+    Restart, // 0x11
+    Shutdown = BASE,
+    #[enum_from(u8 = 0xfe | 0xff)]
+    Close, // 0x21 as u16
+    #[enum_from]
+    Data(u32), // Not converted from integers
+    #[enum_from(skip)]
+    Unknown, // Not converted from integers
+}
+
+fn main() {
+    assert_eq!(Message::from(Source::Ping), Message::Ping);
+    assert_eq!(Message::from(Source::Data(1)), Message::Data(1));
+
+    assert_eq!(Message::try_from(0u8), Ok(Message::Ping));
+    assert_eq!(Message::try_from(1u8), Ok(Message::Pong));
+    assert_eq!(Message::try_from(0x10u8), Ok(Message::Reset));
+    assert_eq!(Message::try_from(0x11u8), Ok(Message::Restart));
+    assert_eq!(Message::try_from(0x20u8), Ok(Message::Shutdown));
+    assert_eq!(Message::try_from(0xfeu8), Ok(Message::Close));
+    assert_eq!(Message::try_from(0xffu8), Ok(Message::Close));
+    assert_eq!(Message::try_from(0x21u8), Err(0x21));
+    assert_eq!(Message::try_from(0x22u8), Err(0x22));
+
+    assert_eq!(Message::try_from(0x11u16), Ok(Message::Restart));
+    assert_eq!(Message::try_from(0x21u16), Ok(Message::Close));
+    assert_eq!(Message::try_from(0xffu16), Err(0xff));
+}
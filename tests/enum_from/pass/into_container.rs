@@ -0,0 +1,37 @@
+use enum_convert::EnumFrom;
+
+enum Source {
+    Name(String),
+    Bytes { data: String },
+    Nested(Option<i32>),
+}
+
+#[derive(Debug, PartialEq, EnumFrom)]
+#[enum_from(Source)]
+enum Target {
+    #[enum_from]
+    Name(Option<String>),
+    #[enum_from]
+    Bytes { data: Vec<u8> },
+    #[enum_from]
+    Nested(Option<i32>),
+}
+
+fn main() {
+    assert_eq!(
+        Target::from(Source::Name("name".to_string())),
+        Target::Name(Some("name".to_string())),
+    );
+    assert_eq!(
+        Target::from(Source::Bytes {
+            data: "ab".to_string()
+        }),
+        Target::Bytes {
+            data: vec![b'a', b'b']
+        },
+    );
+    assert_eq!(
+        Target::from(Source::Nested(Some(1))),
+        Target::Nested(Some(1))
+    );
+}
@@ -0,0 +1,119 @@
+use enum_convert::{EnumFrom, EnumMapping, FieldMapping, VariantMapping};
+
+enum Source {
+    Unit,
+    Tuple(i32, &'static str),
+    Struct { x: i32, y: i32 },
+    Code(u16),
+}
+
+enum Registers {
+    First = 1,
+    Second = 2,
+}
+
+#[derive(EnumFrom)]
+#[enum_from(Source, mapping)]
+#[enum_from(Registers { First, Second }, by_discriminant, mapping)]
+#[repr(u8)]
+enum Target {
+    #[enum_from(Source)]
+    Unit = 1,
+    #[enum_from(Source::Code(404))]
+    NotFound = 2,
+    #[enum_from(Source)]
+    Tuple(
+        #[enum_from(Source::Tuple.1)] String,
+        #[enum_from(Source::Tuple.0)] i64,
+    ),
+    #[enum_from(Source::Struct)]
+    Point {
+        #[enum_from(Source::Struct.x)]
+        a: i64,
+        y: i64,
+    },
+    #[enum_from(Source::Code)]
+    Code(u16),
+    Extra,
+}
+
+fn main() {
+    assert_eq!(
+        <Source as EnumMapping<Target>>::VARIANTS,
+        &[
+            VariantMapping {
+                source: "Code",
+                target: "NotFound",
+                fields: &[],
+            },
+            VariantMapping {
+                source: "Code",
+                target: "Code",
+                fields: &[FieldMapping {
+                    source: "0",
+                    target: "0"
+                }],
+            },
+            VariantMapping {
+                source: "Struct",
+                target: "Point",
+                fields: &[
+                    FieldMapping {
+                        source: "x",
+                        target: "a"
+                    },
+                    FieldMapping {
+                        source: "y",
+                        target: "y"
+                    }
+                ],
+            },
+            VariantMapping {
+                source: "Tuple",
+                target: "Tuple",
+                fields: &[
+                    FieldMapping {
+                        source: "1",
+                        target: "0"
+                    },
+                    FieldMapping {
+                        source: "0",
+                        target: "1"
+                    }
+                ],
+            },
+            VariantMapping {
+                source: "Unit",
+                target: "Unit",
+                fields: &[],
+            },
+        ]
+    );
+
+    assert_eq!(
+        <Registers as EnumMapping<Target>>::VARIANTS,
+        &[
+            VariantMapping {
+                source: "First",
+                target: "Unit",
+                fields: &[],
+            },
+            VariantMapping {
+                source: "Second",
+                target: "NotFound",
+                fields: &[],
+            },
+        ]
+    );
+
+    // The variants of Target which are not converted from Source.
+    let unmapped = ["Unit", "NotFound", "Tuple", "Point", "Code", "Extra"]
+        .into_iter()
+        .filter(|variant| {
+            !<Source as EnumMapping<Target>>::VARIANTS
+                .iter()
+                .any(|mapping| mapping.target == *variant)
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(unmapped, ["Extra"]);
+}
@@ -0,0 +1,71 @@
+use enum_convert::EnumFrom;
+
+enum FsKind {
+    Full,
+    Corrupted(u8),
+}
+
+enum IoKind {
+    NotFound,
+    Timeout(u32),
+    Denied { path: &'static str },
+    Fs(FsKind),
+}
+
+enum Source {
+    Io(IoKind),
+    Other(&'static str),
+}
+
+#[derive(EnumFrom)]
+#[enum_from(Source)]
+enum Target {
+    #[enum_from(Source::Io(IoKind::NotFound))]
+    NotFound,
+    #[enum_from(Source::Io(IoKind::Timeout))]
+    Timeout(u64),
+    #[enum_from(Source::Io(IoKind::Denied))]
+    Denied {
+        #[enum_from(IoKind::Denied.path)]
+        file: String,
+    },
+    #[enum_from(Source::Io(IoKind::Fs(FsKind::Full)))]
+    DiskFull,
+    #[enum_from(Source::Io(IoKind::Fs(FsKind::Corrupted(0))))]
+    Wiped,
+    #[enum_from(Source::Io(IoKind::Fs(FsKind::Corrupted)))]
+    Corrupted(u8),
+    #[enum_from]
+    Other(String),
+}
+
+fn main() {
+    assert!(matches!(
+        Target::from(Source::Io(IoKind::NotFound)),
+        Target::NotFound
+    ));
+    assert!(matches!(
+        Target::from(Source::Io(IoKind::Timeout(10))),
+        Target::Timeout(10)
+    ));
+    assert!(matches!(
+        Target::from(Source::Io(IoKind::Denied { path: "/root" })),
+        Target::Denied { ref file } if file == "/root",
+    ));
+    assert!(matches!(
+        Target::from(Source::Io(IoKind::Fs(FsKind::Full))),
+        Target::DiskFull
+    ));
+    assert!(matches!(
+        Target::from(Source::Io(IoKind::Fs(FsKind::Corrupted(0)))),
+        Target::Wiped
+    ));
+    assert!(matches!(
+        Target::from(Source::Io(IoKind::Fs(FsKind::Corrupted(3)))),
+        Target::Corrupted(3)
+    ));
+    assert!(matches!(
+        Target::from(Source::Other("other")),
+        Target::Other(ref s) if s == "other",
+    ));
+}
@@ -0,0 +1,55 @@
+use enum_convert::EnumFrom;
+
+enum Source {
+    Code(u16),
+    Opcode(u8, bool),
+    Named { id: u32 },
+}
+
+#[derive(EnumFrom)]
+#[enum_from(Source)]
+enum Target {
+    #[enum_from(Source::Code(404))]
+    NotFound,
+    #[enum_from(Source::Code(500..=599))]
+    ServerError,
+    #[enum_from]
+    Code(u32),
+    #[enum_from(Source::Opcode(0x01, _), Source::Opcode(0x02, true))]
+    Read,
+    #[enum_from(Source::Opcode(_, _))]
+    Unknown,
+    #[enum_from(Source::Named { id: 0 })]
+    Anonymous,
+    #[enum_from(Source::Named)]
+    Named { id: u64 },
+}
+
+fn main() {
+    assert!(matches!(Target::from(Source::Code(404)), Target::NotFound));
+    assert!(matches!(
+        Target::from(Source::Code(503)),
+        Target::ServerError
+    ));
+    assert!(matches!(Target::from(Source::Code(200)), Target::Code(200)));
+    assert!(matches!(
+        Target::from(Source::Opcode(1, false)),
+        Target::Read
+    ));
+    assert!(matches!(
+        Target::from(Source::Opcode(2, true)),
+        Target::Read
+    ));
+    assert!(matches!(
+        Target::from(Source::Opcode(2, false)),
+        Target::Unknown
+    ));
+    assert!(matches!(
+        Target::from(Source::Named { id: 0 }),
+        Target::Anonymous
+    ));
+    assert!(matches!(
+        Target::from(Source::Named { id: 1 }),
+        Target::Named { id: 1 }
+    ));
+}
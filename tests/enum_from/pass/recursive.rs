@@ -0,0 +1,37 @@
+use enum_convert::EnumFrom;
+
+enum Expr {
+    Literal(i32),
+    Neg(Box<Expr>),
+    Add { lhs: Box<Expr>, rhs: Box<Expr> },
+}
+
+#[derive(Debug, PartialEq, EnumFrom)]
+#[enum_from(Expr)]
+enum TypedExpr {
+    #[enum_from]
+    Literal(i64),
+    #[enum_from]
+    Neg(#[enum_from(map)] Box<Self>),
+    #[enum_from]
+    Add {
+        #[enum_from(map)]
+        lhs: Box<TypedExpr>,
+        #[enum_from(map)]
+        rhs: Box<TypedExpr>,
+    },
+}
+
+fn main() {
+    let expr = Expr::Add {
+        lhs: Box::new(Expr::Literal(1)),
+        rhs: Box::new(Expr::Neg(Box::new(Expr::Literal(2)))),
+    };
+    assert_eq!(
+        TypedExpr::from(expr),
+        TypedExpr::Add {
+            lhs: Box::new(TypedExpr::Literal(1)),
+            rhs: Box::new(TypedExpr::Neg(Box::new(TypedExpr::Literal(2)))),
+        }
+    );
+}
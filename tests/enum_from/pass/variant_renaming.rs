@@ -0,0 +1,59 @@
+use enum_convert::EnumFrom;
+
+#[allow(non_camel_case_types)]
+enum FfiStatus {
+    STATUS_OK,
+    STATUS_NOT_FOUND,
+    STATUS_HTTP_ERROR(i32),
+}
+
+enum Kind {
+    KindRead,
+    KindWrite,
+}
+
+enum Failure {
+    IoError,
+    ParseError { line: usize },
+}
+
+#[derive(Debug, PartialEq, EnumFrom)]
+#[enum_from(FfiStatus, rename_all = "SCREAMING_SNAKE_CASE", add_prefix = "STATUS_")]
+enum Status {
+    #[enum_from]
+    Ok,
+    #[enum_from]
+    NotFound,
+    #[enum_from]
+    HTTPError(i64),
+}
+
+#[derive(Debug, PartialEq, EnumFrom)]
+#[enum_from(Kind, add_prefix = "Kind")]
+#[enum_from(Failure, add_suffix = "Error")]
+enum Operation {
+    #[enum_from(Kind)]
+    Read,
+    #[enum_from(Kind)]
+    Write,
+    #[enum_from(Failure)]
+    Io,
+    #[enum_from(Failure)]
+    Parse { line: usize },
+}
+
+fn main() {
+    assert_eq!(Status::from(FfiStatus::STATUS_OK), Status::Ok);
+    assert_eq!(Status::from(FfiStatus::STATUS_NOT_FOUND), Status::NotFound);
+    assert_eq!(
+        Status::from(FfiStatus::STATUS_HTTP_ERROR(500)),
+        Status::HTTPError(500)
+    );
+    assert_eq!(Operation::from(Kind::KindRead), Operation::Read);
+    assert_eq!(Operation::from(Kind::KindWrite), Operation::Write);
+    assert_eq!(Operation::from(Failure::IoError), Operation::Io);
+    assert_eq!(
+        Operation::from(Failure::ParseError { line: 1 }),
+        Operation::Parse { line: 1 }
+    );
+}
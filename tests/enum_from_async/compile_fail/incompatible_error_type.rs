@@ -0,0 +1,18 @@
+use enum_convert::EnumFromAsync;
+
+enum Source {
+    Tuple(i32),
+}
+
+async fn convert(value: i32) -> Result<i64, String> {
+    Ok(value.into())
+}
+
+#[derive(EnumFromAsync)]
+#[enum_from(Source)] // Missing error = String
+enum Target {
+    #[enum_from]
+    Tuple(#[enum_from(with = convert)] i64),
+}
+
+fn main() {}
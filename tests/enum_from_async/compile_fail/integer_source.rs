@@ -0,0 +1,14 @@
+use enum_convert::EnumFromAsync;
+
+enum Source {
+    Unit,
+}
+
+#[derive(EnumFromAsync)]
+#[enum_from(Source, u8)]
+enum Target {
+    #[enum_from]
+    Unit,
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+use enum_convert::EnumFromAsync;
+
+enum Source {
+    Tuple(Vec<i32>),
+}
+
+async fn convert(value: Vec<i32>) -> Result<Vec<i64>, std::convert::Infallible> {
+    Ok(value.into_iter().map(Into::into).collect())
+}
+
+#[derive(EnumFromAsync)]
+#[enum_from(Source)]
+enum Target {
+    #[enum_from]
+    Tuple(#[enum_from(with = convert, map)] Vec<i64>),
+}
+
+fn main() {}
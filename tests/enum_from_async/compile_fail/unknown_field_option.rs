@@ -0,0 +1,18 @@
+use enum_convert::EnumFromAsync;
+
+enum Source {
+    Tuple(i32),
+}
+
+async fn convert(value: i32) -> Result<i64, String> {
+    Ok(value.into())
+}
+
+#[derive(EnumFromAsync)]
+#[enum_from(Source, error = String)]
+enum Target {
+    #[enum_from]
+    Tuple(#[enum_from(using = convert)] i64), // Should be with = convert
+}
+
+fn main() {}
@@ -0,0 +1,49 @@
+use std::{
+    pin::pin,
+    task::{Context, Poll, Waker},
+};
+
+use enum_convert::{EnumFromAsync, FromAsync};
+
+enum Source {
+    Unit,
+    Tuple(i32, &'static str),
+    Struct { x: i32, y: i32 },
+}
+
+#[derive(EnumFromAsync)]
+#[enum_from(Source)]
+enum Target {
+    #[enum_from]
+    Unit,
+    #[enum_from]
+    Tuple(i64, String),
+    #[enum_from]
+    Struct {
+        x: i64,
+        y: i64,
+    },
+    Extra,
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    match pin!(future).poll(&mut Context::from_waker(Waker::noop())) {
+        Poll::Ready(output) => output,
+        Poll::Pending => panic!("The future should be ready"),
+    }
+}
+
+fn main() {
+    assert!(matches!(
+        block_on(Target::from_async(Source::Unit)),
+        Ok(Target::Unit),
+    ));
+    assert!(matches!(
+        block_on(Target::from_async(Source::Tuple(42, "hello"))),
+        Ok(Target::Tuple(42, ref s)) if s == "hello",
+    ));
+    assert!(matches!(
+        block_on(Target::from_async(Source::Struct { x: 1, y: 2 })),
+        Ok(Target::Struct { x: 1, y: 2 }),
+    ));
+}
@@ -0,0 +1,95 @@
+use std::{
+    collections::HashMap,
+    pin::pin,
+    task::{Context, Poll, Waker},
+};
+
+use enum_convert::{EnumFromAsync, FromAsync};
+
+#[derive(Debug, PartialEq)]
+enum ResolveError {
+    UnknownUser(u32),
+    UnknownGroup(u32),
+}
+
+#[derive(Debug, PartialEq)]
+struct UnknownGroup(u32);
+
+impl From<UnknownGroup> for ResolveError {
+    fn from(UnknownGroup(id): UnknownGroup) -> Self {
+        ResolveError::UnknownGroup(id)
+    }
+}
+
+async fn resolve_user(id: u32) -> Result<String, ResolveError> {
+    let users = HashMap::from([(1, "alice"), (2, "bob")]);
+    users
+        .get(&id)
+        .map(|name| name.to_string())
+        .ok_or(ResolveError::UnknownUser(id))
+}
+
+async fn resolve_group(id: u32) -> Result<String, UnknownGroup> {
+    match id {
+        1 => Ok("admins".to_string()),
+        _ => Err(UnknownGroup(id)),
+    }
+}
+
+enum FirstSource {
+    Joined(u32, u32),
+    Left { user: u32 },
+}
+
+enum SecondSource {
+    Left { user_id: u32 },
+}
+
+#[derive(Debug, PartialEq, EnumFromAsync)]
+#[enum_from(FirstSource, SecondSource, error = ResolveError)]
+enum Target {
+    #[enum_from(FirstSource)]
+    Joined(
+        #[enum_from(with = resolve_user)] String,
+        #[enum_from(with = resolve_group)] String,
+    ),
+    #[enum_from(FirstSource, SecondSource)]
+    Left {
+        #[enum_from(SecondSource::Left.user_id, with = resolve_user)]
+        user: String,
+    },
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    match pin!(future).poll(&mut Context::from_waker(Waker::noop())) {
+        Poll::Ready(output) => output,
+        Poll::Pending => panic!("The future should be ready"),
+    }
+}
+
+fn main() {
+    assert_eq!(
+        block_on(Target::from_async(FirstSource::Joined(1, 1))),
+        Ok(Target::Joined("alice".to_string(), "admins".to_string())),
+    );
+    assert_eq!(
+        block_on(Target::from_async(FirstSource::Joined(3, 1))),
+        Err(ResolveError::UnknownUser(3)),
+    );
+    assert_eq!(
+        block_on(Target::from_async(FirstSource::Joined(1, 2))),
+        Err(ResolveError::UnknownGroup(2)),
+    );
+    assert_eq!(
+        block_on(Target::from_async(FirstSource::Left { user: 2 })),
+        Ok(Target::Left {
+            user: "bob".to_string()
+        }),
+    );
+    assert_eq!(
+        block_on(Target::from_async(SecondSource::Left { user_id: 1 })),
+        Ok(Target::Left {
+            user: "alice".to_string()
+        }),
+    );
+}
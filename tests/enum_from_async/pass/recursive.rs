@@ -0,0 +1,59 @@
+use std::{
+    collections::VecDeque,
+    pin::pin,
+    task::{Context, Poll, Waker},
+};
+
+use enum_convert::{EnumFromAsync, FromAsync};
+
+enum Expr {
+    Literal(i32),
+    Neg(Box<Expr>),
+    Sum(Vec<Expr>),
+    Product(VecDeque<Expr>),
+}
+
+#[derive(Debug, PartialEq, EnumFromAsync)]
+#[enum_from(Expr)]
+enum TypedExpr {
+    #[enum_from]
+    Literal(i64),
+    #[enum_from]
+    Neg(#[enum_from(map)] Box<TypedExpr>),
+    #[enum_from]
+    Sum(#[enum_from(map)] Vec<Self>),
+    #[enum_from]
+    Product(#[enum_from(map)] VecDeque<TypedExpr>),
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    match pin!(future).poll(&mut Context::from_waker(Waker::noop())) {
+        Poll::Ready(output) => output,
+        Poll::Pending => panic!("The future should be ready"),
+    }
+}
+
+fn main() {
+    let expr = Expr::Neg(Box::new(Expr::Neg(Box::new(Expr::Literal(1)))));
+    assert_eq!(
+        block_on(TypedExpr::from_async(expr)),
+        Ok(TypedExpr::Neg(Box::new(TypedExpr::Neg(Box::new(
+            TypedExpr::Literal(1)
+        ))))),
+    );
+
+    let expr = Expr::Sum(vec![
+        Expr::Literal(1),
+        Expr::Product(VecDeque::from([Expr::Literal(2), Expr::Literal(3)])),
+    ]);
+    assert_eq!(
+        block_on(TypedExpr::from_async(expr)),
+        Ok(TypedExpr::Sum(vec![
+            TypedExpr::Literal(1),
+            TypedExpr::Product(VecDeque::from([
+                TypedExpr::Literal(2),
+                TypedExpr::Literal(3)
+            ])),
+        ])),
+    );
+}
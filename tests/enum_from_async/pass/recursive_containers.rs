@@ -0,0 +1,71 @@
+use std::{
+    collections::HashMap,
+    pin::pin,
+    task::{Context, Poll, Waker},
+};
+
+use enum_convert::{EnumFromAsync, FromAsync};
+
+enum Node {
+    Leaf(i32),
+    Branch {
+        parent: Option<Box<Node>>,
+        children: HashMap<&'static str, Node>,
+    },
+    Fallible(Result<Box<Node>, &'static str>),
+    Pair([Box<Node>; 2]),
+}
+
+#[derive(Debug, PartialEq, EnumFromAsync)]
+#[enum_from(Node)]
+enum Tree {
+    #[enum_from]
+    Leaf(i64),
+    #[enum_from]
+    Branch {
+        #[enum_from(map)]
+        parent: Option<Box<Self>>,
+        #[enum_from(map)]
+        children: HashMap<String, Tree>,
+    },
+    #[enum_from]
+    Fallible(#[enum_from(map)] Result<Box<Self>, String>),
+    #[enum_from]
+    Pair(#[enum_from(map)] [Box<Self>; 2]),
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    match pin!(future).poll(&mut Context::from_waker(Waker::noop())) {
+        Poll::Ready(output) => output,
+        Poll::Pending => panic!("The future should be ready"),
+    }
+}
+
+fn main() {
+    let node = Node::Branch {
+        parent: Some(Box::new(Node::Leaf(1))),
+        children: HashMap::from([("left", Node::Fallible(Ok(Box::new(Node::Leaf(2)))))]),
+    };
+    assert_eq!(
+        block_on(Tree::from_async(node)),
+        Ok(Tree::Branch {
+            parent: Some(Box::new(Tree::Leaf(1))),
+            children: HashMap::from([(
+                "left".to_string(),
+                Tree::Fallible(Ok(Box::new(Tree::Leaf(2)))),
+            )]),
+        }),
+    );
+
+    let node = Node::Pair([
+        Box::new(Node::Fallible(Err("missing"))),
+        Box::new(Node::Leaf(3)),
+    ]);
+    assert_eq!(
+        block_on(Tree::from_async(node)),
+        Ok(Tree::Pair([
+            Box::new(Tree::Fallible(Err("missing".to_string()))),
+            Box::new(Tree::Leaf(3)),
+        ])),
+    );
+}
@@ -0,0 +1,13 @@
+use enum_convert::EnumInto;
+
+#[derive(EnumInto)]
+#[enum_into(Target, strip_prefix = "Unit")]
+enum Source {
+    Unit,
+}
+
+enum Target {
+    Unit,
+}
+
+fn main() {}
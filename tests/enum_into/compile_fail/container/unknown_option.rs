@@ -0,0 +1,13 @@
+use enum_convert::EnumInto;
+
+#[derive(EnumInto)]
+#[enum_into(Target, prefix = "Target")]
+enum Source {
+    Unit,
+}
+
+enum Target {
+    TargetUnit,
+}
+
+fn main() {}
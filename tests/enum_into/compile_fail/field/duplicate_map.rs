@@ -0,0 +1,13 @@
+use enum_convert::EnumInto;
+
+#[derive(EnumInto)]
+#[enum_into(Target)]
+enum Source {
+    Tuple(#[enum_into(map, map)] Vec<i32>),
+}
+
+enum Target {
+    Tuple(Vec<i64>),
+}
+
+fn main() {}
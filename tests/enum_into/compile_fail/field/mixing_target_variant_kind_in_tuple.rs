@@ -0,0 +1,17 @@
+use enum_convert::EnumInto;
+
+#[derive(EnumInto)]
+#[enum_into(Target)]
+enum Source {
+    #[enum_into(Target::Struct)]
+    Tuple(
+        #[enum_into(Target::Struct.a)] i32,
+        #[enum_into(Target::Struct.1)] i32, // .1 Does not make sense for a Struct target
+    ),
+}
+
+enum Target {
+    Struct { a: i32, b: i32 },
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use enum_convert::EnumInto;
+
+#[derive(EnumInto)]
+#[enum_into(Target)]
+enum Source {
+    #[enum_into(Target::Tuple)]
+    Tuple(
+        #[enum_into(Target::Tuple.1)] i32,
+        #[enum_into(Target::Tuple.a)] i32, // .a Does not make sense for a Tuple target
+    ),
+}
+
+enum Target {
+    Tuple(i32, i32),
+}
+
+fn main() {}
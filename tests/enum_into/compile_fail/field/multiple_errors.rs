@@ -0,0 +1,22 @@
+use enum_convert::EnumInto;
+
+#[derive(EnumInto)]
+#[enum_into(Target)]
+enum Source {
+    Tuple(
+        #[enum_into(Target::Tuple.0, unknown)] i32,
+        #[enum_into(invalid syntax)] i32,
+    ),
+    #[enum_into(Target::Other)]
+    Struct {
+        #[enum_into(Target::Struct.x, unknown)]
+        x: i32,
+    },
+}
+
+enum Target {
+    Tuple(i32, i32),
+    Struct { x: i32 },
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use enum_convert::EnumInto;
+
+#[derive(EnumInto)]
+#[enum_into(Target)]
+enum Source {
+    Tuple(#[enum_into(as_it_is)] i32),
+}
+
+enum Target {
+    Tuple(i32),
+}
+
+fn main() {}
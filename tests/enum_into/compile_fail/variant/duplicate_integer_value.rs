@@ -0,0 +1,12 @@
+use enum_convert::EnumInto;
+
+#[derive(EnumInto)]
+#[enum_into(i32)]
+enum Source {
+    #[enum_into(i32 = 1, i32 = 2)]
+    Unit,
+    #[enum_into(i64 = 3)] // Should be i32
+    Other,
+}
+
+fn main() {}
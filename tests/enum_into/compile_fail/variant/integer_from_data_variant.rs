@@ -0,0 +1,11 @@
+use enum_convert::EnumInto;
+
+#[derive(EnumInto)]
+#[enum_into(u8)]
+enum Opcode {
+    Ping,
+    Data(Vec<u8>), // The payload would be lost
+    Close { code: u16 },
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+use enum_convert::EnumInto;
+
+enum IoKind {
+    NotFound,
+}
+
+enum Target {
+    Io(IoKind),
+}
+
+#[derive(EnumInto)]
+#[enum_into(Target)]
+enum Source {
+    #[enum_into(Target::Io(NotFound))]
+    NotFound,
+}
+
+fn main() {}
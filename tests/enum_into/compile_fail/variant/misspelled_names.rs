@@ -0,0 +1,22 @@
+use enum_convert::EnumInto;
+
+#[derive(EnumInto)]
+#[enum_into(Target)]
+enum Source {
+    #[enum_into(Taregt::Unit)] // Should be Target::Unit
+    Unit,
+    Tuple(#[enum_into(Traget::Tuple.0)] i32), // Should be Target::Tuple.0
+    #[enum_into(Target::Struct)]
+    Struct {
+        #[enum_into(Target::Strcut.a)] // Should be Target::Struct.a
+        x: i32,
+    },
+}
+
+enum Target {
+    Unit,
+    Tuple(i32),
+    Struct { a: i32 },
+}
+
+fn main() {}
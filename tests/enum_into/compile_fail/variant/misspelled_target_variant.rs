@@ -0,0 +1,14 @@
+use enum_convert::EnumInto;
+
+#[derive(EnumInto)]
+#[enum_into(Target)]
+enum Source {
+    #[enum_into(Target::InvalidConfiguraton)] // Should be Target::InvalidConfiguration
+    InvalidConfiguration,
+}
+
+enum Target {
+    InvalidConfiguration,
+}
+
+fn main() {}
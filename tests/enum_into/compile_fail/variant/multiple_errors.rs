@@ -0,0 +1,23 @@
+use enum_convert::EnumInto;
+
+#[derive(EnumInto)]
+#[enum_into(Target)]
+enum Source {
+    #[enum_into(Target, NonExistent)] // Invalid target enum
+    Unit,
+    #[enum_into(Target::Tuple, Other::Tuple)] // Invalid target enum
+    Tuple(i32),
+    Struct {
+        // Should be #[enum_into(Target::Struct.a)]
+        #[enum_into(NonExistent::Struct.a)]
+        x: i32,
+    },
+}
+
+enum Target {
+    Unit,
+    Tuple(i32),
+    Struct { a: i32 },
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use enum_convert::EnumInto;
+
+#[derive(EnumInto)]
+#[enum_into(Target)]
+enum Source {
+    #[enum_into(Target::Tuple)] // A unit variant has no field to convert into the payload
+    Unit,
+}
+
+enum Target {
+    Tuple(i32),
+}
+
+fn main() {}
@@ -0,0 +1,45 @@
+use enum_convert::EnumInto;
+
+#[derive(Debug, PartialEq)]
+struct Id(u32);
+
+#[derive(EnumInto)]
+#[enum_into(Target)]
+#[enum_into(OtherTarget, as_is)]
+enum Source {
+    Created {
+        #[enum_into(as_is)]
+        id: Id,
+        name: &'static str,
+    },
+    Deleted(#[enum_into(as_is)] Id, u8),
+}
+
+#[derive(Debug, PartialEq)]
+enum Target {
+    Created { id: Id, name: String },
+    Deleted(Id, u8),
+}
+
+#[derive(Debug, PartialEq)]
+enum OtherTarget {
+    Created { id: Id, name: &'static str },
+    Deleted(Id, u8),
+}
+
+fn main() {
+    assert_eq!(
+        Target::from(Source::Created {
+            id: Id(1),
+            name: "name"
+        }),
+        Target::Created {
+            id: Id(1),
+            name: "name".to_string()
+        },
+    );
+    assert_eq!(
+        OtherTarget::from(Source::Deleted(Id(2), 3)),
+        OtherTarget::Deleted(Id(2), 3)
+    );
+}
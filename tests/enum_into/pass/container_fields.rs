@@ -0,0 +1,73 @@
+use std::collections::HashMap;
+
+use enum_convert::EnumInto;
+
+#[derive(Debug, PartialEq)]
+struct Small(i32);
+
+#[derive(Debug, PartialEq)]
+struct Big(i64);
+
+impl From<Small> for Big {
+    fn from(Small(value): Small) -> Self {
+        Big(value.into())
+    }
+}
+
+#[derive(EnumInto)]
+#[enum_into(Target)]
+enum Source {
+    Sequence(#[enum_into(map)] Vec<Small>),
+    Map {
+        #[enum_into(map)]
+        by_name: HashMap<&'static str, Small>,
+    },
+    Optional(#[enum_into(map)] Option<[Small; 1]>),
+    Fallible(#[enum_into(map)] Result<Small, &'static str>),
+    Expr(
+        #[enum_into(map)] Vec<Source>,
+        #[enum_into(map)] Option<Box<Self>>,
+    ),
+}
+
+#[derive(Debug, PartialEq)]
+enum Target {
+    Sequence(Vec<Big>),
+    Map { by_name: HashMap<String, Big> },
+    Optional(Option<[Big; 1]>),
+    Fallible(Result<Big, String>),
+    Expr(Vec<Target>, Option<Box<Target>>),
+}
+
+fn main() {
+    assert_eq!(
+        Target::from(Source::Sequence(vec![Small(1)])),
+        Target::Sequence(vec![Big(1)]),
+    );
+    assert_eq!(
+        Target::from(Source::Map {
+            by_name: HashMap::from([("one", Small(1))]),
+        }),
+        Target::Map {
+            by_name: HashMap::from([("one".to_string(), Big(1))]),
+        },
+    );
+    assert_eq!(
+        Target::from(Source::Optional(Some([Small(1)]))),
+        Target::Optional(Some([Big(1)])),
+    );
+    assert_eq!(
+        Target::from(Source::Fallible(Ok(Small(1)))),
+        Target::Fallible(Ok(Big(1))),
+    );
+    assert_eq!(
+        Target::from(Source::Expr(
+            vec![Source::Sequence(vec![])],
+            Some(Box::new(Source::Fallible(Err("failed")))),
+        )),
+        Target::Expr(
+            vec![Target::Sequence(vec![])],
+            Some(Box::new(Target::Fallible(Err("failed".to_string())))),
+        ),
+    );
+}
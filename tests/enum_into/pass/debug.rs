@@ -0,0 +1,24 @@
+use enum_convert::EnumInto;
+
+#[derive(Debug)]
+enum Target {
+    Unit,
+    Pair(u16, u8),
+}
+
+#[derive(EnumInto)]
+#[enum_into(Target, debug)]
+enum Source {
+    #[enum_into]
+    Unit,
+    #[enum_into(Target::Pair)]
+    Tuple(u8, u8),
+}
+
+fn main() {
+    assert!(matches!(Target::from(Source::Unit), Target::Unit));
+    assert!(matches!(
+        Target::from(Source::Tuple(1, 2)),
+        Target::Pair(1, 2)
+    ));
+}
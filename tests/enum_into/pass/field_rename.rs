@@ -7,6 +7,11 @@ enum Source {
         #[enum_into(Target::Tuple.1)] i32,
         #[enum_into(Target::Tuple.0)] i32,
     ),
+    Rotated(
+        #[enum_into(Target::Rotated.1)] i32,
+        #[enum_into(Target::Rotated.2)] &'static str,
+        #[enum_into(Target::Rotated.0)] bool,
+    ),
     Struct {
         #[enum_into(Target::Struct.a)]
         x: i32,
@@ -17,6 +22,7 @@ enum Source {
 
 enum Target {
     Tuple(i32, i32),
+    Rotated(bool, i64, String),
     Struct { a: i64, b: i64 },
     Extra,
 }
@@ -26,6 +32,10 @@ fn main() {
         Target::from(Source::Tuple(1, 2)),
         Target::Tuple(a, b) if a == 2 && b == 1,
     ));
+    assert!(matches!(
+        Target::from(Source::Rotated(1, "hello", true)),
+        Target::Rotated(true, 1, ref s) if s == "hello",
+    ));
     assert!(matches!(
         Target::from(Source::Struct { x: 1, y: 2}),
         Target::Struct { a, b } if a == 1 && b == 2,
@@ -0,0 +1,37 @@
+use enum_convert::EnumInto;
+
+#[derive(EnumInto)]
+#[enum_into(Target, rename_all_fields = "camelCase")]
+enum Source {
+    UserCreated {
+        user_id: u32,
+        display_name: &'static str,
+        #[enum_into(Target::UserCreated.admin)]
+        is_admin: bool,
+    },
+}
+
+#[derive(Debug, PartialEq)]
+#[allow(non_snake_case)]
+enum Target {
+    UserCreated {
+        userId: u64,
+        displayName: String,
+        admin: bool,
+    },
+}
+
+fn main() {
+    assert_eq!(
+        Target::from(Source::UserCreated {
+            user_id: 1,
+            display_name: "name",
+            is_admin: false,
+        }),
+        Target::UserCreated {
+            userId: 1,
+            displayName: "name".to_string(),
+            admin: false,
+        },
+    );
+}
@@ -0,0 +1,22 @@
+use enum_convert::EnumInto;
+
+#[derive(EnumInto)]
+#[enum_into(i32, u8)]
+#[repr(u16)]
+enum Status {
+    Ok, // 0
+    Created = 10,
+    Accepted, // 11
+    #[enum_into(i32 = -1)]
+    Failed, // 12 as u8
+}
+
+fn main() {
+    assert_eq!(i32::from(Status::Ok), 0);
+    assert_eq!(i32::from(Status::Created), 10);
+    assert_eq!(i32::from(Status::Accepted), 11);
+    assert_eq!(i32::from(Status::Failed), -1);
+
+    assert_eq!(u8::from(Status::Accepted), 11);
+    assert_eq!(u8::from(Status::Failed), 12);
+}
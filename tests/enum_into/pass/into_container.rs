@@ -0,0 +1,35 @@
+use std::{collections::VecDeque, net::Ipv4Addr};
+
+use enum_convert::EnumInto;
+
+#[derive(EnumInto)]
+#[enum_into(Target)]
+enum Source {
+    Name(String),
+    Address([u8; 4]),
+    Queue { items: Vec<i32> },
+}
+
+#[derive(Debug, PartialEq)]
+enum Target {
+    Name(Option<String>),
+    Address(Ipv4Addr),
+    Queue { items: VecDeque<i32> },
+}
+
+fn main() {
+    assert_eq!(
+        Target::from(Source::Name("name".to_string())),
+        Target::Name(Some("name".to_string())),
+    );
+    assert_eq!(
+        Target::from(Source::Address([127, 0, 0, 1])),
+        Target::Address(Ipv4Addr::LOCALHOST),
+    );
+    assert_eq!(
+        Target::from(Source::Queue { items: vec![1, 2] }),
+        Target::Queue {
+            items: VecDeque::from([1, 2])
+        },
+    );
+}
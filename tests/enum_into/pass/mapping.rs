@@ -0,0 +1,65 @@
+use enum_convert::{EnumInto, EnumMapping, FieldMapping, VariantMapping};
+
+#[derive(EnumInto)]
+#[enum_into(Target, mapping)]
+enum Source {
+    Unit,
+    #[enum_into(Target::Pair)]
+    Tuple(
+        #[enum_into(Target::Pair.1)] i32,
+        #[enum_into(Target::Pair.0)] &'static str,
+    ),
+    #[enum_into(Target::Point)]
+    Struct {
+        #[enum_into(Target::Point.a)]
+        x: i32,
+        y: i32,
+    },
+}
+
+enum Target {
+    Unit,
+    Pair(String, i64),
+    Point { a: i64, y: i64 },
+}
+
+fn main() {
+    assert_eq!(
+        <Source as EnumMapping<Target>>::VARIANTS,
+        &[
+            VariantMapping {
+                source: "Struct",
+                target: "Point",
+                fields: &[
+                    FieldMapping {
+                        source: "x",
+                        target: "a"
+                    },
+                    FieldMapping {
+                        source: "y",
+                        target: "y"
+                    }
+                ],
+            },
+            VariantMapping {
+                source: "Tuple",
+                target: "Pair",
+                fields: &[
+                    FieldMapping {
+                        source: "0",
+                        target: "1"
+                    },
+                    FieldMapping {
+                        source: "1",
+                        target: "0"
+                    }
+                ],
+            },
+            VariantMapping {
+                source: "Unit",
+                target: "Unit",
+                fields: &[],
+            },
+        ]
+    );
+}
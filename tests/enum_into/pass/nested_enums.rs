@@ -0,0 +1,52 @@
+use enum_convert::EnumInto;
+
+#[derive(Debug, PartialEq)]
+enum Kind {
+    Deep,
+}
+
+#[derive(Debug, PartialEq)]
+enum IoKind {
+    NotFound,
+    Denied { path: String },
+    Nested(Kind),
+}
+
+#[derive(Debug, PartialEq)]
+enum Target {
+    Io(IoKind),
+    Other(String),
+}
+
+#[derive(EnumInto)]
+#[enum_into(Target)]
+enum Source {
+    #[enum_into(Target::Io(IoKind::NotFound))]
+    NotFound,
+    #[enum_into(Target::Io(IoKind::Denied))]
+    Denied {
+        #[enum_into(IoKind::Denied.path)]
+        file: &'static str,
+    },
+    #[enum_into(Target::Io(IoKind::Nested(Kind::Deep)))]
+    Deep,
+    Other(&'static str),
+}
+
+fn main() {
+    assert_eq!(Target::from(Source::NotFound), Target::Io(IoKind::NotFound));
+    assert_eq!(
+        Target::from(Source::Denied { file: "/etc" }),
+        Target::Io(IoKind::Denied {
+            path: "/etc".to_string()
+        }),
+    );
+    assert_eq!(
+        Target::from(Source::Deep),
+        Target::Io(IoKind::Nested(Kind::Deep))
+    );
+    assert_eq!(
+        Target::from(Source::Other("other")),
+        Target::Other("other".to_string())
+    );
+}
@@ -0,0 +1,38 @@
+use enum_convert::EnumInto;
+
+#[derive(EnumInto)]
+#[enum_into(TypedExpr)]
+enum Expr {
+    Literal(i32),
+    Neg(#[enum_into(map)] Box<Self>),
+    Add {
+        #[enum_into(map)]
+        lhs: Box<Expr>,
+        #[enum_into(map)]
+        rhs: Box<Expr>,
+    },
+}
+
+#[derive(Debug, PartialEq)]
+enum TypedExpr {
+    Literal(i64),
+    Neg(Box<TypedExpr>),
+    Add {
+        lhs: Box<TypedExpr>,
+        rhs: Box<TypedExpr>,
+    },
+}
+
+fn main() {
+    let expr = Expr::Add {
+        lhs: Box::new(Expr::Literal(1)),
+        rhs: Box::new(Expr::Neg(Box::new(Expr::Literal(2)))),
+    };
+    assert_eq!(
+        TypedExpr::from(expr),
+        TypedExpr::Add {
+            lhs: Box::new(TypedExpr::Literal(1)),
+            rhs: Box::new(TypedExpr::Neg(Box::new(TypedExpr::Literal(2)))),
+        }
+    );
+}
@@ -0,0 +1,41 @@
+use enum_convert::EnumInto;
+
+#[derive(EnumInto)]
+#[enum_into(FfiStatus, rename_all = "SCREAMING_SNAKE_CASE", add_prefix = "STATUS_")]
+#[enum_into(Kind, strip_suffix = "Status", add_suffix = "Kind")]
+enum Status {
+    Ok,
+    NotFound,
+    #[enum_into(Kind::Unexpected)]
+    UnknownStatus(i32),
+}
+
+#[derive(Debug, PartialEq)]
+#[allow(non_camel_case_types)]
+enum FfiStatus {
+    STATUS_OK,
+    STATUS_NOT_FOUND,
+    STATUS_UNKNOWN_STATUS(i64),
+}
+
+#[derive(Debug, PartialEq)]
+enum Kind {
+    OkKind,
+    NotFoundKind,
+    Unexpected(i32),
+}
+
+fn main() {
+    assert_eq!(FfiStatus::from(Status::Ok), FfiStatus::STATUS_OK);
+    assert_eq!(
+        FfiStatus::from(Status::NotFound),
+        FfiStatus::STATUS_NOT_FOUND
+    );
+    assert_eq!(
+        FfiStatus::from(Status::UnknownStatus(1)),
+        FfiStatus::STATUS_UNKNOWN_STATUS(1)
+    );
+    assert_eq!(Kind::from(Status::Ok), Kind::OkKind);
+    assert_eq!(Kind::from(Status::NotFound), Kind::NotFoundKind);
+    assert_eq!(Kind::from(Status::UnknownStatus(1)), Kind::Unexpected(1));
+}
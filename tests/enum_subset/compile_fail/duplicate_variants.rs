@@ -0,0 +1,15 @@
+#[enum_convert::register]
+enum Internal {
+    Unit,
+    Other,
+}
+
+enum_convert::enum_subset! {
+    pub enum Public from Internal {
+        Unit,
+        Unit = Other,
+        Renamed = Unit,
+    }
+}
+
+fn main() {}
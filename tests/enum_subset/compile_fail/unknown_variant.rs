@@ -0,0 +1,15 @@
+#[enum_convert::register]
+enum Internal {
+    Unit,
+    InvalidConfiguration,
+}
+
+enum_convert::enum_subset! {
+    pub enum Public from Internal {
+        Unit,
+        Invalid = InvalidConfiguraton, // Should be InvalidConfiguration
+        Missing,
+    }
+}
+
+fn main() {}
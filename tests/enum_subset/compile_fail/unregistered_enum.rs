@@ -0,0 +1,9 @@
+enum Internal {
+    Unit,
+}
+
+enum_convert::enum_subset! {
+    pub enum Public from Internal { Unit }
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+#[enum_convert::register]
+#[derive(Debug, PartialEq)]
+enum Internal {
+    Unit,
+    Tuple(i32),
+}
+
+enum_convert::enum_subset! {
+    #[derive(Debug, PartialEq)]
+    enum Public from Internal { Unit, Tuple }
+}
+
+fn main() {
+    assert_eq!(Internal::from(Public::Unit), Internal::Unit);
+    assert_eq!(Public::try_from(Internal::Tuple(1)), Ok(Public::Tuple(1)));
+}
@@ -0,0 +1,23 @@
+use exported_enums::event::{Event, Payload};
+
+enum_convert::enum_subset! {
+    #[derive(Debug, PartialEq)]
+    pub enum PublicEvent from ::exported_enums::event::Event {
+        Updated,
+        Removed = Deleted,
+    }
+}
+
+fn main() {
+    assert_eq!(
+        Event::from(PublicEvent::Updated(1, Payload(2))),
+        Event::Updated(1, Payload(2))
+    );
+    assert_eq!(Event::from(PublicEvent::Removed(1)), Event::Deleted(1));
+
+    assert_eq!(
+        PublicEvent::try_from(Event::Deleted(1)),
+        Ok(PublicEvent::Removed(1))
+    );
+    assert_eq!(PublicEvent::try_from(Event::Migrated), Err(Event::Migrated));
+}
@@ -0,0 +1,59 @@
+mod internal {
+    #[derive(Debug, PartialEq)]
+    pub struct Payload(pub u32);
+
+    #[enum_convert::register]
+    #[derive(Debug, PartialEq)]
+    pub enum Event {
+        Created { id: u32, name: String },
+        Updated(u32, Payload),
+        Deleted(u32),
+        Migrated,
+    }
+}
+
+mod public {
+    use crate::internal::Payload;
+
+    enum_convert::enum_subset! {
+        /// The events exposed to the users.
+        #[derive(Debug, PartialEq)]
+        pub enum PublicEvent from crate::internal::Event {
+            Created,
+            Updated,
+            /// Renamed from `Deleted`.
+            Removed = Deleted,
+        }
+    }
+}
+
+use internal::{Event, Payload};
+use public::PublicEvent;
+
+fn main() {
+    assert_eq!(
+        Event::from(PublicEvent::Created {
+            id: 1,
+            name: "first".to_string()
+        }),
+        Event::Created {
+            id: 1,
+            name: "first".to_string()
+        }
+    );
+    assert_eq!(
+        Event::from(PublicEvent::Updated(1, Payload(2))),
+        Event::Updated(1, Payload(2))
+    );
+    assert_eq!(Event::from(PublicEvent::Removed(1)), Event::Deleted(1));
+
+    assert_eq!(
+        PublicEvent::try_from(Event::Updated(1, Payload(2))),
+        Ok(PublicEvent::Updated(1, Payload(2)))
+    );
+    assert_eq!(
+        PublicEvent::try_from(Event::Deleted(1)),
+        Ok(PublicEvent::Removed(1))
+    );
+    assert_eq!(PublicEvent::try_from(Event::Migrated), Err(Event::Migrated));
+}
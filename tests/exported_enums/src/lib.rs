@@ -0,0 +1,15 @@
+//! Enums exported to the tests of `enum_convert` from another crate.
+
+pub mod event {
+    #[derive(Debug, PartialEq)]
+    pub struct Payload(pub u32);
+
+    #[enum_convert_derive::register(export)]
+    #[derive(Debug, PartialEq)]
+    pub enum Event {
+        Created { id: u32, name: String },
+        Updated(u32, Payload),
+        Deleted(u32),
+        Migrated,
+    }
+}
@@ -0,0 +1,84 @@
+use std::{
+    pin::pin,
+    task::{Context, Poll, Waker},
+};
+
+use enum_convert::FromAsync;
+use no_std_enums::{AsyncExpr, Expr, Leaf, Opcode, TypedExpr, shadowed};
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    match pin!(future).poll(&mut Context::from_waker(Waker::noop())) {
+        Poll::Ready(output) => output,
+        Poll::Pending => panic!("The future should be ready"),
+    }
+}
+
+#[test]
+fn enum_from() {
+    let expr = Expr::Negate(Box::new(Expr::Sum(vec![
+        Expr::Literal(1),
+        Expr::Point { x: 2, y: 3 },
+    ])));
+    assert_eq!(
+        TypedExpr::from(expr),
+        TypedExpr::Negate(Box::new(TypedExpr::Sum(vec![
+            TypedExpr::Literal(1),
+            TypedExpr::Coordinates { abscissa: 2, y: 3 },
+        ]))),
+    );
+}
+
+#[test]
+fn enum_into() {
+    let expr: Expr = TypedExpr::Negate(Box::new(TypedExpr::Invalid)).into();
+    assert_eq!(expr, Expr::Negate(Box::new(Expr::Unknown)));
+}
+
+#[test]
+fn enum_from_async() {
+    let expr = Expr::Negate(Box::new(Expr::Negate(Box::new(Expr::Literal(1)))));
+    assert_eq!(
+        block_on(AsyncExpr::from_async(expr)),
+        Ok(AsyncExpr::Negate(Box::new(AsyncExpr::Negate(Box::new(
+            AsyncExpr::Literal(1)
+        ))))),
+    );
+    let expr = Expr::Sum(vec![Expr::Literal(1), Expr::Point { x: 2, y: 3 }]);
+    assert_eq!(
+        block_on(AsyncExpr::from_async(expr)),
+        Ok(AsyncExpr::Sum(vec![
+            AsyncExpr::Literal(1),
+            AsyncExpr::Coordinates { abscissa: 2, y: 3 },
+        ])),
+    );
+    assert_eq!(
+        block_on(AsyncExpr::from_async(Expr::Unknown)),
+        Ok(AsyncExpr::Invalid)
+    );
+}
+
+#[test]
+fn integers() {
+    assert_eq!(Opcode::try_from(1), Ok(Opcode::Pong));
+    assert_eq!(Opcode::try_from(0xfe), Ok(Opcode::Close));
+    assert_eq!(Opcode::try_from(2), Err(2));
+    assert_eq!(u8::from(Opcode::Close), 0xff);
+}
+
+#[test]
+fn enum_subset() {
+    assert_eq!(Leaf::try_from(Expr::Literal(1)), Ok(Leaf::Literal(1)));
+    assert_eq!(Leaf::try_from(Expr::Unknown), Err(Expr::Unknown));
+    assert_eq!(
+        Expr::from(Leaf::Point { x: 1, y: 2 }),
+        Expr::Point { x: 1, y: 2 }
+    );
+}
+
+#[test]
+fn shadowed_prelude() {
+    let target = shadowed::Target::from(shadowed::Source::Value(1));
+    assert!(matches!(target, shadowed::Target::Value(1)));
+    let wide: shadowed::Wide = target.into();
+    assert!(matches!(wide, shadowed::Wide::Value(1)));
+}
@@ -0,0 +1,126 @@
+//! Enums converted by `enum_convert` in a `no_std` crate, which only builds when the generated
+//! code does not refer to `std` nor to the prelude.
+#![no_std]
+
+extern crate alloc;
+
+use alloc::{boxed::Box, vec::Vec};
+
+use enum_convert::{EnumFrom, EnumFromAsync, EnumInto};
+
+#[enum_convert::register]
+#[derive(Debug, PartialEq)]
+pub enum Expr {
+    Literal(i32),
+    Negate(Box<Expr>),
+    Sum(Vec<Expr>),
+    Point { x: i32, y: i32 },
+    Unknown,
+}
+
+#[derive(Debug, PartialEq, EnumFrom, EnumInto)]
+#[enum_from(Expr)]
+#[enum_into(Expr)]
+pub enum TypedExpr {
+    #[enum_from]
+    #[enum_into]
+    Literal(i32),
+    #[enum_from]
+    #[enum_into]
+    Negate(
+        #[enum_from(map)]
+        #[enum_into(map)]
+        Box<Self>,
+    ),
+    #[enum_from]
+    #[enum_into]
+    Sum(
+        #[enum_from(map)]
+        #[enum_into(map)]
+        Vec<TypedExpr>,
+    ),
+    #[enum_from(Expr::Point)]
+    #[enum_into(Expr::Point)]
+    Coordinates {
+        #[enum_from(Expr::Point.x)]
+        #[enum_into(Expr::Point.x)]
+        abscissa: i32,
+        y: i32,
+    },
+    #[enum_from(Expr::Unknown)]
+    #[enum_into(Expr::Unknown)]
+    Invalid,
+}
+
+#[derive(Debug, PartialEq, EnumFromAsync)]
+#[enum_from(Expr)]
+pub enum AsyncExpr {
+    #[enum_from]
+    Literal(i64),
+    #[enum_from]
+    Negate(#[enum_from(map)] Box<Self>),
+    #[enum_from]
+    Sum(#[enum_from(map)] Vec<AsyncExpr>),
+    #[enum_from(Expr::Point)]
+    Coordinates {
+        #[enum_from(Expr::Point.x)]
+        abscissa: i64,
+        y: i64,
+    },
+    #[enum_from(Expr::Unknown)]
+    Invalid,
+}
+
+#[derive(Debug, PartialEq, EnumFrom, EnumInto)]
+#[enum_from(u8)]
+#[enum_into(u8)]
+pub enum Opcode {
+    Ping,
+    Pong,
+    #[enum_from(u8 = 0xfe | 0xff)]
+    #[enum_into(u8 = 0xff)]
+    Close,
+}
+
+enum_convert::enum_subset! {
+    #[derive(Debug, PartialEq)]
+    pub enum Leaf from Expr {
+        Literal,
+        Point,
+    }
+}
+
+/// Enums converted in a module without the prelude, where `From` and `Into` are local traits.
+pub mod shadowed {
+    #![no_implicit_prelude]
+
+    use ::enum_convert::{EnumFrom, EnumInto};
+
+    pub trait From<T> {}
+
+    pub trait Into<T> {}
+
+    pub type Result = ();
+
+    pub enum Source {
+        Unit,
+        Value(i32),
+    }
+
+    pub enum Wide {
+        Unit,
+        Value(i128),
+    }
+
+    #[derive(EnumFrom, EnumInto)]
+    #[enum_from(Source)]
+    #[enum_into(Wide)]
+    pub enum Target {
+        #[enum_from]
+        #[enum_into]
+        Unit,
+        #[enum_from]
+        #[enum_into]
+        Value(i64),
+    }
+}
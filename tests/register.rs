@@ -0,0 +1,11 @@
+#[test]
+fn compile_fail_tests() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/register/compile_fail/**/*.rs");
+}
+
+#[test]
+fn pass_tests() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/register/pass/**/*.rs");
+}
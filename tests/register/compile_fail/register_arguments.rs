@@ -0,0 +1,11 @@
+#[enum_convert::register(Target)]
+enum Source {
+    Unit,
+}
+
+#[enum_convert::register(export, Target)]
+enum Other {
+    Unit,
+}
+
+fn main() {}
@@ -0,0 +1,6 @@
+#[enum_convert::register]
+struct Source {
+    x: i32,
+}
+
+fn main() {}
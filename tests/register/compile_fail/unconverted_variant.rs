@@ -0,0 +1,19 @@
+use enum_convert::EnumFrom;
+
+#[enum_convert::register]
+enum Source {
+    Unit,
+    Tuple(i32),
+    Struct { x: i32 },
+}
+
+#[derive(EnumFrom)]
+#[enum_from(Source, registered)] // Source::Struct is not converted
+enum Target {
+    #[enum_from]
+    Unit,
+    #[enum_from]
+    Tuple(i32),
+}
+
+fn main() {}
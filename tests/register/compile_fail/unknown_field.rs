@@ -0,0 +1,22 @@
+use enum_convert::EnumFrom;
+
+#[enum_convert::register]
+enum Source {
+    Struct {
+        first_name: String,
+        last_name: String,
+    },
+}
+
+#[derive(EnumFrom)]
+#[enum_from(Source, registered)]
+enum Target {
+    #[enum_from]
+    Struct {
+        #[enum_from(Source::Struct.first_nmae)] // Should be first_name
+        first: String,
+        last_name: String,
+    },
+}
+
+fn main() {}
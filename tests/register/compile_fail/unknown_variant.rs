@@ -0,0 +1,18 @@
+use enum_convert::EnumFrom;
+
+#[enum_convert::register]
+enum Source {
+    Unit,
+    InvalidConfiguration,
+}
+
+#[derive(EnumFrom)]
+#[enum_from(Source, registered)]
+enum Target {
+    #[enum_from]
+    Unit,
+    #[enum_from(Source::InvalidConfiguraton)] // Should be Source::InvalidConfiguration
+    InvalidConfiguration,
+}
+
+fn main() {}
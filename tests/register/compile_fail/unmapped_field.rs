@@ -0,0 +1,18 @@
+use enum_convert::EnumFrom;
+
+#[enum_convert::register]
+enum Source {
+    Tuple(i32, i32),
+    Struct { x: i32, y: i32 },
+}
+
+#[derive(EnumFrom)]
+#[enum_from(Source, registered)]
+enum Target {
+    #[enum_from]
+    Tuple(i32), // Source::Tuple.1 is not mapped
+    #[enum_from]
+    Struct { x: i32 }, // Source::Struct.y is not mapped
+}
+
+fn main() {}
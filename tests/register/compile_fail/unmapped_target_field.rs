@@ -0,0 +1,17 @@
+use enum_convert::EnumInto;
+
+#[enum_convert::register]
+enum Target {
+    Struct { a: i32, b: i32 },
+}
+
+#[derive(EnumInto)]
+#[enum_into(Target, registered)]
+enum Source {
+    Struct {
+        #[enum_into(Target::Struct.a)]
+        x: i32,
+    },
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use enum_convert::EnumInto;
+
+#[enum_convert::register]
+enum Target {
+    Unit(i32),
+    Tuple { a: i32 },
+}
+
+#[derive(EnumInto)]
+#[enum_into(Target, registered)]
+enum Source {
+    Unit,
+    Tuple(i32),
+}
+
+fn main() {}
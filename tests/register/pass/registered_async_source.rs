@@ -0,0 +1,36 @@
+use std::{
+    pin::pin,
+    task::{Context, Poll, Waker},
+};
+
+use enum_convert::{EnumFromAsync, FromAsync};
+
+#[enum_convert::register]
+enum Source {
+    Unit,
+    Tuple(i32),
+}
+
+#[derive(Debug, PartialEq, EnumFromAsync)]
+#[enum_from(Source, registered)]
+enum Target {
+    #[enum_from]
+    Unit,
+    #[enum_from]
+    Tuple(i64),
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    match pin!(future).poll(&mut Context::from_waker(Waker::noop())) {
+        Poll::Ready(output) => output,
+        Poll::Pending => panic!("The future should be ready"),
+    }
+}
+
+fn main() {
+    assert_eq!(block_on(Target::from_async(Source::Unit)), Ok(Target::Unit));
+    assert_eq!(
+        block_on(Target::from_async(Source::Tuple(1))),
+        Ok(Target::Tuple(1))
+    );
+}
@@ -0,0 +1,49 @@
+use enum_convert::EnumFrom;
+
+mod source {
+    #[enum_convert::register]
+    #[derive(Debug)]
+    pub enum Source {
+        Unit,
+        Tuple(i32, &'static str),
+        Struct { x: i32, y: i32 },
+        Code(u16),
+        Other,
+    }
+}
+
+use source::Source;
+
+#[derive(Debug, PartialEq, EnumFrom)]
+#[enum_from(Source, registered)]
+enum Target {
+    #[enum_from(Source::Unit, Source::Other)]
+    Unit,
+    #[enum_from]
+    Tuple(i64, String),
+    #[enum_from(Source::Struct)]
+    Renamed {
+        #[enum_from(Source::Struct.x)]
+        a: i64,
+        y: i64,
+    },
+    #[enum_from(Source::Code(404))]
+    NotFound,
+    #[enum_from(Source::Code)]
+    Code(u16),
+}
+
+fn main() {
+    assert_eq!(Target::from(Source::Unit), Target::Unit);
+    assert_eq!(Target::from(Source::Other), Target::Unit);
+    assert_eq!(
+        Target::from(Source::Tuple(1, "hello")),
+        Target::Tuple(1, "hello".to_string())
+    );
+    assert_eq!(
+        Target::from(Source::Struct { x: 1, y: 2 }),
+        Target::Renamed { a: 1, y: 2 }
+    );
+    assert_eq!(Target::from(Source::Code(404)), Target::NotFound);
+    assert_eq!(Target::from(Source::Code(500)), Target::Code(500));
+}
@@ -0,0 +1,47 @@
+use enum_convert::EnumInto;
+
+#[enum_convert::register]
+#[derive(Debug, PartialEq)]
+enum Target {
+    Unit,
+    Tuple(i64, String),
+    Struct { a: i64, b: i64 },
+    Wrapper(Inner),
+    Extra,
+}
+
+#[derive(Debug, PartialEq)]
+enum Inner {
+    Value(i64),
+}
+
+#[derive(EnumInto)]
+#[enum_into(Target, registered)]
+enum Source {
+    Unit,
+    Tuple(i32, &'static str),
+    Struct {
+        #[enum_into(Target::Struct.a)]
+        x: i32,
+        #[enum_into(Target::Struct.b)]
+        y: i32,
+    },
+    #[enum_into(Target::Wrapper(Inner::Value))]
+    Value(i32),
+}
+
+fn main() {
+    assert_eq!(Target::from(Source::Unit), Target::Unit);
+    assert_eq!(
+        Target::from(Source::Tuple(1, "hello")),
+        Target::Tuple(1, "hello".to_string())
+    );
+    assert_eq!(
+        Target::from(Source::Struct { x: 1, y: 2 }),
+        Target::Struct { a: 1, b: 2 }
+    );
+    assert_eq!(
+        Target::from(Source::Value(1)),
+        Target::Wrapper(Inner::Value(1))
+    );
+}